@@ -23,6 +23,21 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("best16;tol3", cortexm4::sync16_tol3::<0xFFFF>());
     run_test("best16;tol4", cortexm4::sync16_tol4::<0xFFFF>());
 
+    // The masked selections, compared with the masked popcount in the single and bit-sliced detectors.
+    run_test("best16;maskedexact", cortexm4::sync16_masked_tol0::<0xFFFF, 0xF0FF>());
+    run_test("best16;maskedtol1", cortexm4::sync16_masked_tol1::<0xFFFF, 0xF0FF>());
+    run_test("best16;maskedtol2", cortexm4::sync16_masked_tol2::<0xFFFF, 0xF0FF>());
+    run_test("best16;maskedtol3", cortexm4::sync16_masked_tol3::<0xFFFF, 0xF0FF>());
+    run_test("best16;maskedtol4", cortexm4::sync16_masked_tol4::<0xFFFF, 0xF0FF>());
+    run_test("single16;maskedpopcnt1", Single16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 1>>::new());
+    run_test("single16;maskedpopcnt2", Single16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 2>>::new());
+    run_test("single16;maskedpopcnt3", Single16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 3>>::new());
+    run_test("single16;maskedpopcnt4", Single16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 4>>::new());
+    run_test("bitsliced16;maskedpopcnt1", BitSliced16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 1>>::new());
+    run_test("bitsliced16;maskedpopcnt2", BitSliced16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 2>>::new());
+    run_test("bitsliced16;maskedpopcnt3", BitSliced16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 3>>::new());
+    run_test("bitsliced16;maskedpopcnt4", BitSliced16Detector::<MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 4>>::new());
+
     run_test("single16;exact", Single16Detector::<Exact16Comparator::<0xFFFF>>::new());
     run_test("single16;lzc1", Single16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 1>>::new());
     run_test("single16;lzc2", Single16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 2>>::new());
@@ -36,6 +51,29 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("single16;twoscmpl2", Single16Detector::<TwosComplement16Comparator::<0xFFFF, 2>>::new());
     run_test("single16;twoscmpl3", Single16Detector::<TwosComplement16Comparator::<0xFFFF, 3>>::new());
     run_test("single16;twoscmpl4", Single16Detector::<TwosComplement16Comparator::<0xFFFF, 4>>::new());
+    run_test("single16;lutpopcnt1", Single16Detector::<LutPopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("single16;lutpopcnt2", Single16Detector::<LutPopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("single16;lutpopcnt3", Single16Detector::<LutPopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("single16;lutpopcnt4", Single16Detector::<LutPopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("single16;swarpopcnt1", Single16Detector::<SwarPopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("single16;swarpopcnt2", Single16Detector::<SwarPopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("single16;swarpopcnt3", Single16Detector::<SwarPopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("single16;swarpopcnt4", Single16Detector::<SwarPopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("single16;eepopcnt1", Single16Detector::<EarlyExitPopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("single16;eepopcnt2", Single16Detector::<EarlyExitPopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("single16;eepopcnt3", Single16Detector::<EarlyExitPopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("single16;eepopcnt4", Single16Detector::<EarlyExitPopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("single16;weighted2", cortexm4::sync16_weighted::<0xFFFF, 2>([1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2]));
+    run_test("single16;weighted4", cortexm4::sync16_weighted::<0xFFFF, 4>([1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2]));
+    run_test("single16;rtexact", Single16Detector::with_comparator(RuntimeExact16Comparator { syncword: runtime(0xFFFF) }));
+    run_test("single16;rtpopcnt1", Single16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("single16;rtpopcnt2", Single16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("single16;rtpopcnt3", Single16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("single16;rtpopcnt4", Single16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
+    run_test("single16;rttwoscmpl1", Single16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("single16;rttwoscmpl2", Single16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("single16;rttwoscmpl3", Single16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("single16;rttwoscmpl4", Single16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
 
     run_test("double16;exact", Double16Detector::<Exact16Comparator::<0xFFFF>>::new());
     run_test("double16;lzc1", Double16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 1>>::new());
@@ -50,6 +88,69 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("double16;twoscmpl2", Double16Detector::<TwosComplement16Comparator::<0xFFFF, 2>>::new());
     run_test("double16;twoscmpl3", Double16Detector::<TwosComplement16Comparator::<0xFFFF, 3>>::new());
     run_test("double16;twoscmpl4", Double16Detector::<TwosComplement16Comparator::<0xFFFF, 4>>::new());
+    run_test("double16;rtexact", Double16Detector::with_comparator(RuntimeExact16Comparator { syncword: runtime(0xFFFF) }));
+    run_test("double16;rtpopcnt1", Double16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("double16;rtpopcnt2", Double16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("double16;rtpopcnt3", Double16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("double16;rtpopcnt4", Double16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
+    run_test("double16;rttwoscmpl1", Double16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("double16;rttwoscmpl2", Double16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("double16;rttwoscmpl3", Double16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("double16;rttwoscmpl4", Double16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
+
+    run_test("bitsliced16;popcnt0", BitSliced16Detector::<PopCount16Comparator::<0xFFFF, 0>>::new());
+    run_test("bitsliced16;popcnt1", BitSliced16Detector::<PopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("bitsliced16;popcnt2", BitSliced16Detector::<PopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("bitsliced16;popcnt3", BitSliced16Detector::<PopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("bitsliced16;popcnt4", BitSliced16Detector::<PopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("bitsliced16;rtpopcnt2", BitSliced16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+
+    run_test("bitap16;popcnt0", Bitap16Detector::<PopCount16Comparator::<0xFFFF, 0>>::new());
+    run_test("bitap16;popcnt1", Bitap16Detector::<PopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("bitap16;popcnt2", Bitap16Detector::<PopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("bitap16;popcnt3", Bitap16Detector::<PopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("bitap16;popcnt4", Bitap16Detector::<PopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("bitap16;rtpopcnt2", Bitap16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+
+    run_test("lut16;byte0", LutDetector::<ByteTables16<0xFFFF>>::new(0));
+    run_test("lut16;byte2", LutDetector::<ByteTables16<0xFFFF>>::new(2));
+    run_test("lut16;nibble0", LutDetector::<NibbleTables16<0xFFFF>>::new(0));
+    run_test("lut16;nibble2", LutDetector::<NibbleTables16<0xFFFF>>::new(2));
+    println!("lut16;byte;size;{}", ByteTables16::<0xFFFF>::SIZE);
+    println!("lut16;nibble;size;{}", NibbleTables16::<0xFFFF>::SIZE);
+
+    run_test("quad16;exact", Quad16Detector::<Exact16Comparator::<0xFFFF>>::new());
+    run_test("quad16;lzc1", Quad16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("quad16;lzc2", Quad16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("quad16;lzc3", Quad16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("quad16;lzc4", Quad16Detector::<LeadingZeroCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("quad16;popcnt1", Quad16Detector::<PopCount16Comparator::<0xFFFF, 1>>::new());
+    run_test("quad16;popcnt2", Quad16Detector::<PopCount16Comparator::<0xFFFF, 2>>::new());
+    run_test("quad16;popcnt3", Quad16Detector::<PopCount16Comparator::<0xFFFF, 3>>::new());
+    run_test("quad16;popcnt4", Quad16Detector::<PopCount16Comparator::<0xFFFF, 4>>::new());
+    run_test("quad16;twoscmpl1", Quad16Detector::<TwosComplement16Comparator::<0xFFFF, 1>>::new());
+    run_test("quad16;twoscmpl2", Quad16Detector::<TwosComplement16Comparator::<0xFFFF, 2>>::new());
+    run_test("quad16;twoscmpl3", Quad16Detector::<TwosComplement16Comparator::<0xFFFF, 3>>::new());
+    run_test("quad16;twoscmpl4", Quad16Detector::<TwosComplement16Comparator::<0xFFFF, 4>>::new());
+    run_test("quad16;rtexact", Quad16Detector::with_comparator(RuntimeExact16Comparator { syncword: runtime(0xFFFF) }));
+    run_test("quad16;rtpopcnt1", Quad16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("quad16;rtpopcnt2", Quad16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("quad16;rtpopcnt3", Quad16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("quad16;rtpopcnt4", Quad16Detector::with_comparator(RuntimePopCount16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
+    run_test("quad16;rttwoscmpl1", Quad16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(1) }));
+    run_test("quad16;rttwoscmpl2", Quad16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(2) }));
+    run_test("quad16;rttwoscmpl3", Quad16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(3) }));
+    run_test("quad16;rttwoscmpl4", Quad16Detector::with_comparator(RuntimeTwosComplement16Comparator { syncword: runtime(0xFFFF), threshold: runtime(4) }));
+
+    run_test("single8;exact", Single8Detector::<Exact8Comparator::<0xFF>>::new());
+    run_test("single8;popcnt1", Single8Detector::<PopCount8Comparator::<0xFF, 1>>::new());
+    run_test("single8;popcnt2", Single8Detector::<PopCount8Comparator::<0xFF, 2>>::new());
+    run_test("double8;exact", Double8Detector::<Exact8Comparator::<0xFF>>::new());
+    run_test("double8;popcnt1", Double8Detector::<PopCount8Comparator::<0xFF, 1>>::new());
+    run_test("double8;popcnt2", Double8Detector::<PopCount8Comparator::<0xFF, 2>>::new());
+    run_test("quad8;exact", Quad8Detector::<Exact8Comparator::<0xFF>>::new());
+    run_test("quad8;popcnt1", Quad8Detector::<PopCount8Comparator::<0xFF, 1>>::new());
+    run_test("quad8;popcnt2", Quad8Detector::<PopCount8Comparator::<0xFF, 2>>::new());
 
     run_test("best32;exact", cortexm4::sync32_tol0::<0xFFFFFFFF>());
     run_test("best32;tol1", cortexm4::sync32_tol1::<0xFFFFFFFF>());
@@ -59,6 +160,27 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("best32;tol5", cortexm4::sync32_tol5::<0xFFFFFFFF>());
     run_test("best32;tol6", cortexm4::sync32_tol6::<0xFFFFFFFF>());
 
+    // The masked selections, compared with the masked popcount in the single and bit-sliced detectors.
+    run_test("best32;maskedexact", cortexm4::sync32_masked_tol0::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol1", cortexm4::sync32_masked_tol1::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol2", cortexm4::sync32_masked_tol2::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol3", cortexm4::sync32_masked_tol3::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol4", cortexm4::sync32_masked_tol4::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol5", cortexm4::sync32_masked_tol5::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("best32;maskedtol6", cortexm4::sync32_masked_tol6::<0xFFFFFFFF, 0xFFFFFFF0>());
+    run_test("single32;maskedpopcnt1", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 1>>::new());
+    run_test("single32;maskedpopcnt2", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 2>>::new());
+    run_test("single32;maskedpopcnt3", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 3>>::new());
+    run_test("single32;maskedpopcnt4", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 4>>::new());
+    run_test("single32;maskedpopcnt5", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 5>>::new());
+    run_test("single32;maskedpopcnt6", Single32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 6>>::new());
+    run_test("bitsliced32;maskedpopcnt1", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 1>>::new());
+    run_test("bitsliced32;maskedpopcnt2", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 2>>::new());
+    run_test("bitsliced32;maskedpopcnt3", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 3>>::new());
+    run_test("bitsliced32;maskedpopcnt4", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 4>>::new());
+    run_test("bitsliced32;maskedpopcnt5", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 5>>::new());
+    run_test("bitsliced32;maskedpopcnt6", BitSliced32Detector::<MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFFFFF0, 6>>::new());
+
     run_test("single32;exact", Single32Detector::<Exact32Comparator::<0xFFFFFFFF>>::new());
     run_test("single32;lzc1", Single32Detector::<LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>>::new());
     run_test("single32;lzc2", Single32Detector::<LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>>::new());
@@ -78,6 +200,39 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("single32;twoscmpl4", Single32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 4>>::new());
     run_test("single32;twoscmpl5", Single32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 5>>::new());
     run_test("single32;twoscmpl6", Single32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("single32;lutpopcnt1", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 1>>::new());
+    run_test("single32;lutpopcnt2", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 2>>::new());
+    run_test("single32;lutpopcnt3", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 3>>::new());
+    run_test("single32;lutpopcnt4", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 4>>::new());
+    run_test("single32;lutpopcnt5", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 5>>::new());
+    run_test("single32;lutpopcnt6", Single32Detector::<LutPopCount32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("single32;swarpopcnt1", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 1>>::new());
+    run_test("single32;swarpopcnt2", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 2>>::new());
+    run_test("single32;swarpopcnt3", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 3>>::new());
+    run_test("single32;swarpopcnt4", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 4>>::new());
+    run_test("single32;swarpopcnt5", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 5>>::new());
+    run_test("single32;swarpopcnt6", Single32Detector::<SwarPopCount32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("single32;eepopcnt1", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 1>>::new());
+    run_test("single32;eepopcnt2", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 2>>::new());
+    run_test("single32;eepopcnt3", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 3>>::new());
+    run_test("single32;eepopcnt4", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 4>>::new());
+    run_test("single32;eepopcnt5", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 5>>::new());
+    run_test("single32;eepopcnt6", Single32Detector::<EarlyExitPopCount32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("single32;weighted2", cortexm4::sync32_weighted::<0xFFFFFFFF, 2>([1; 32]));
+    run_test("single32;weighted6", cortexm4::sync32_weighted::<0xFFFFFFFF, 6>([1; 32]));
+    run_test("single32;rtexact", Single32Detector::with_comparator(RuntimeExact32Comparator { syncword: runtime(0xFFFFFFFF) }));
+    run_test("single32;rtpopcnt1", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(1) }));
+    run_test("single32;rtpopcnt2", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+    run_test("single32;rtpopcnt3", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(3) }));
+    run_test("single32;rtpopcnt4", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(4) }));
+    run_test("single32;rtpopcnt5", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(5) }));
+    run_test("single32;rtpopcnt6", Single32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(6) }));
+    run_test("single32;rttwoscmpl1", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(1) }));
+    run_test("single32;rttwoscmpl2", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+    run_test("single32;rttwoscmpl3", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(3) }));
+    run_test("single32;rttwoscmpl4", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(4) }));
+    run_test("single32;rttwoscmpl5", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(5) }));
+    run_test("single32;rttwoscmpl6", Single32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(6) }));
 
     run_test("double32;exact", Double32Detector::<Exact32Comparator::<0xFFFFFFFF>>::new());
     run_test("double32;lzc1", Double32Detector::<LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>>::new());
@@ -98,6 +253,118 @@ pub fn handler(reg: Regs, thr_init: ThrsInit) {
     run_test("double32;twoscmpl4", Double32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 4>>::new());
     run_test("double32;twoscmpl5", Double32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 5>>::new());
     run_test("double32;twoscmpl6", Double32Detector::<TwosComplement32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("double32;rtexact", Double32Detector::with_comparator(RuntimeExact32Comparator { syncword: runtime(0xFFFFFFFF) }));
+    run_test("double32;rtpopcnt1", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(1) }));
+    run_test("double32;rtpopcnt2", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+    run_test("double32;rtpopcnt3", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(3) }));
+    run_test("double32;rtpopcnt4", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(4) }));
+    run_test("double32;rtpopcnt5", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(5) }));
+    run_test("double32;rtpopcnt6", Double32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(6) }));
+    run_test("double32;rttwoscmpl1", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(1) }));
+    run_test("double32;rttwoscmpl2", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+    run_test("double32;rttwoscmpl3", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(3) }));
+    run_test("double32;rttwoscmpl4", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(4) }));
+    run_test("double32;rttwoscmpl5", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(5) }));
+    run_test("double32;rttwoscmpl6", Double32Detector::with_comparator(RuntimeTwosComplement32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(6) }));
+
+    run_test("bitsliced32;popcnt0", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 0>>::new());
+    run_test("bitsliced32;popcnt1", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 1>>::new());
+    run_test("bitsliced32;popcnt2", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 2>>::new());
+    run_test("bitsliced32;popcnt3", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 3>>::new());
+    run_test("bitsliced32;popcnt4", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 4>>::new());
+    run_test("bitsliced32;popcnt5", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 5>>::new());
+    run_test("bitsliced32;popcnt6", BitSliced32Detector::<PopCount32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("bitsliced32;rtpopcnt2", BitSliced32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+
+    run_test("bitap32;popcnt0", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 0>>::new());
+    run_test("bitap32;popcnt1", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 1>>::new());
+    run_test("bitap32;popcnt2", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 2>>::new());
+    run_test("bitap32;popcnt3", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 3>>::new());
+    run_test("bitap32;popcnt4", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 4>>::new());
+    run_test("bitap32;popcnt5", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 5>>::new());
+    run_test("bitap32;popcnt6", Bitap32Detector::<PopCount32Comparator::<0xFFFFFFFF, 6>>::new());
+    run_test("bitap32;rtpopcnt2", Bitap32Detector::with_comparator(RuntimePopCount32Comparator { syncword: runtime(0xFFFFFFFF), threshold: runtime(2) }));
+
+    run_test("lut32;byte0", LutDetector::<ByteTables32<0xFFFFFFFF>>::new(0));
+    run_test("lut32;byte2", LutDetector::<ByteTables32<0xFFFFFFFF>>::new(2));
+    run_test("lut32;nibble0", LutDetector::<NibbleTables32<0xFFFFFFFF>>::new(0));
+    run_test("lut32;nibble2", LutDetector::<NibbleTables32<0xFFFFFFFF>>::new(2));
+    println!("lut32;byte;size;{}", ByteTables32::<0xFFFFFFFF>::SIZE);
+    println!("lut32;nibble;size;{}", NibbleTables32::<0xFFFFFFFF>::SIZE);
+
+    run_test("best64;exact", cortexm4::sync64_tol0::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol1", cortexm4::sync64_tol1::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol2", cortexm4::sync64_tol2::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol3", cortexm4::sync64_tol3::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol4", cortexm4::sync64_tol4::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol5", cortexm4::sync64_tol5::<0xFFFFFFFFFFFFFFFF>());
+    run_test("best64;tol6", cortexm4::sync64_tol6::<0xFFFFFFFFFFFFFFFF>());
+
+    run_test("single64;exact", Single64Detector::<Exact64Comparator::<0xFFFFFFFFFFFFFFFF>>::new());
+    run_test("single64;lzc1", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("single64;lzc2", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("single64;lzc3", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("single64;lzc4", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("single64;lzc5", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("single64;lzc6", Single64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("single64;popcnt1", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("single64;popcnt2", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("single64;popcnt3", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("single64;popcnt4", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("single64;popcnt5", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("single64;popcnt6", Single64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("single64;twoscmpl1", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("single64;twoscmpl2", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("single64;twoscmpl3", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("single64;twoscmpl4", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("single64;twoscmpl5", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("single64;twoscmpl6", Single64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("single64;rtexact", Single64Detector::with_comparator(RuntimeExact64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF) }));
+    run_test("single64;rtpopcnt1", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(1) }));
+    run_test("single64;rtpopcnt2", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(2) }));
+    run_test("single64;rtpopcnt3", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(3) }));
+    run_test("single64;rtpopcnt4", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(4) }));
+    run_test("single64;rtpopcnt5", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(5) }));
+    run_test("single64;rtpopcnt6", Single64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(6) }));
+    run_test("single64;rttwoscmpl1", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(1) }));
+    run_test("single64;rttwoscmpl2", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(2) }));
+    run_test("single64;rttwoscmpl3", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(3) }));
+    run_test("single64;rttwoscmpl4", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(4) }));
+    run_test("single64;rttwoscmpl5", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(5) }));
+    run_test("single64;rttwoscmpl6", Single64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(6) }));
+
+    run_test("double64;exact", Double64Detector::<Exact64Comparator::<0xFFFFFFFFFFFFFFFF>>::new());
+    run_test("double64;lzc1", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("double64;lzc2", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("double64;lzc3", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("double64;lzc4", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("double64;lzc5", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("double64;lzc6", Double64Detector::<LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("double64;popcnt1", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("double64;popcnt2", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("double64;popcnt3", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("double64;popcnt4", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("double64;popcnt5", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("double64;popcnt6", Double64Detector::<PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("double64;twoscmpl1", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 1>>::new());
+    run_test("double64;twoscmpl2", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>>::new());
+    run_test("double64;twoscmpl3", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 3>>::new());
+    run_test("double64;twoscmpl4", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 4>>::new());
+    run_test("double64;twoscmpl5", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 5>>::new());
+    run_test("double64;twoscmpl6", Double64Detector::<TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 6>>::new());
+    run_test("double64;rtexact", Double64Detector::with_comparator(RuntimeExact64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF) }));
+    run_test("double64;rtpopcnt1", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(1) }));
+    run_test("double64;rtpopcnt2", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(2) }));
+    run_test("double64;rtpopcnt3", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(3) }));
+    run_test("double64;rtpopcnt4", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(4) }));
+    run_test("double64;rtpopcnt5", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(5) }));
+    run_test("double64;rtpopcnt6", Double64Detector::with_comparator(RuntimePopCount64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(6) }));
+    run_test("double64;rttwoscmpl1", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(1) }));
+    run_test("double64;rttwoscmpl2", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(2) }));
+    run_test("double64;rttwoscmpl3", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(3) }));
+    run_test("double64;rttwoscmpl4", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(4) }));
+    run_test("double64;rttwoscmpl5", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(5) }));
+    run_test("double64;rttwoscmpl6", Double64Detector::with_comparator(RuntimeTwosComplement64Comparator { syncword: runtime(0xFFFFFFFFFFFFFFFF), threshold: runtime(6) }));
 
     // Enter a sleep state on ISR exit.
     reg.scb_scr.sleeponexit.set_bit();
@@ -115,4 +382,9 @@ fn run_test<D: Detector<T>, T>(name: &str, detector: D) {
     }
     sw.stop();
     println!("{};{}", name, sw.elapsed());
+}
+
+/// Hide a value from the optimizer, so that runtime comparators are not specialized for a constant syncword.
+fn runtime<T: Copy>(value: T) -> T {
+    unsafe { core::ptr::read_volatile(&value) }
 }
\ No newline at end of file
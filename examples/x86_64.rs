@@ -0,0 +1,110 @@
+//! Benchmark of the detectors on an x86_64 host, used for the selection in `detectors::x86_64`.
+//!
+//! cargo run --release --features std --example x86_64
+
+#![feature(bench_black_box)]
+
+use std::{hint::black_box, mem::size_of, time::Instant};
+
+use drone_framesync::{comparators::*, detectors::*};
+
+macro_rules! for_thresholds16 {
+    ($name:ident, $detector:ident) => {
+        run_test(concat!(stringify!($name), ";popcnt1"), $detector::<PopCount16Comparator<0xFFFF, 1>>::new());
+        run_test(concat!(stringify!($name), ";popcnt2"), $detector::<PopCount16Comparator<0xFFFF, 2>>::new());
+        run_test(concat!(stringify!($name), ";popcnt3"), $detector::<PopCount16Comparator<0xFFFF, 3>>::new());
+        run_test(concat!(stringify!($name), ";popcnt4"), $detector::<PopCount16Comparator<0xFFFF, 4>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl1"), $detector::<TwosComplement16Comparator<0xFFFF, 1>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl2"), $detector::<TwosComplement16Comparator<0xFFFF, 2>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl3"), $detector::<TwosComplement16Comparator<0xFFFF, 3>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl4"), $detector::<TwosComplement16Comparator<0xFFFF, 4>>::new());
+    };
+}
+
+macro_rules! for_thresholds32 {
+    ($name:ident, $detector:ident) => {
+        run_test(concat!(stringify!($name), ";popcnt1"), $detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new());
+        run_test(concat!(stringify!($name), ";popcnt2"), $detector::<PopCount32Comparator<0xFFFFFFFF, 2>>::new());
+        run_test(concat!(stringify!($name), ";popcnt3"), $detector::<PopCount32Comparator<0xFFFFFFFF, 3>>::new());
+        run_test(concat!(stringify!($name), ";popcnt4"), $detector::<PopCount32Comparator<0xFFFFFFFF, 4>>::new());
+        run_test(concat!(stringify!($name), ";popcnt5"), $detector::<PopCount32Comparator<0xFFFFFFFF, 5>>::new());
+        run_test(concat!(stringify!($name), ";popcnt6"), $detector::<PopCount32Comparator<0xFFFFFFFF, 6>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl1"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 1>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl2"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 2>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl3"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 3>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl4"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 4>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl5"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 5>>::new());
+        run_test(concat!(stringify!($name), ";twoscmpl6"), $detector::<TwosComplement32Comparator<0xFFFFFFFF, 6>>::new());
+    };
+}
+
+fn main() {
+    run_test("single16;exact", Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+    for_thresholds16!(single16, Single16Detector);
+    for_thresholds16!(double16, Double16Detector);
+    for_thresholds16!(quad16, Quad16Detector);
+    run_test("bitsliced16;popcnt0", BitSliced16Detector::<PopCount16Comparator<0xFFFF, 0>>::new());
+    run_test("bitsliced16;popcnt1", BitSliced16Detector::<PopCount16Comparator<0xFFFF, 1>>::new());
+    run_test("bitsliced16;popcnt2", BitSliced16Detector::<PopCount16Comparator<0xFFFF, 2>>::new());
+    run_test("bitsliced16;popcnt3", BitSliced16Detector::<PopCount16Comparator<0xFFFF, 3>>::new());
+    run_test("bitsliced16;popcnt4", BitSliced16Detector::<PopCount16Comparator<0xFFFF, 4>>::new());
+    run_test("simd16;popcnt0", Simd16Detector::<PopCount16Comparator<0xFFFF, 0>>::new());
+    run_test("simd16;popcnt1", Simd16Detector::<PopCount16Comparator<0xFFFF, 1>>::new());
+    run_test("simd16;popcnt2", Simd16Detector::<PopCount16Comparator<0xFFFF, 2>>::new());
+    run_test("simd16;popcnt3", Simd16Detector::<PopCount16Comparator<0xFFFF, 3>>::new());
+    run_test("simd16;popcnt4", Simd16Detector::<PopCount16Comparator<0xFFFF, 4>>::new());
+
+    run_test("single32;exact", Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+    for_thresholds32!(single32, Single32Detector);
+    for_thresholds32!(double32, Double32Detector);
+    run_test("bitsliced32;popcnt0", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 0>>::new());
+    run_test("bitsliced32;popcnt1", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new());
+    run_test("bitsliced32;popcnt2", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 2>>::new());
+    run_test("bitsliced32;popcnt3", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 3>>::new());
+    run_test("bitsliced32;popcnt4", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 4>>::new());
+    run_test("bitsliced32;popcnt5", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 5>>::new());
+    run_test("bitsliced32;popcnt6", BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 6>>::new());
+    run_test("simd32;popcnt0", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 0>>::new());
+    run_test("simd32;popcnt1", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new());
+    run_test("simd32;popcnt2", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 2>>::new());
+    run_test("simd32;popcnt3", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 3>>::new());
+    run_test("simd32;popcnt4", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 4>>::new());
+    run_test("simd32;popcnt5", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 5>>::new());
+    run_test("simd32;popcnt6", Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 6>>::new());
+}
+
+/// Create a haystack of random bytes, i.e. where the syncword is only found by chance, as in the noise between frames.
+/// A haystack of zeros would let the bit-sliced and SIMD detectors exit early on every block.
+fn haystack(size: usize) -> Vec<u8> {
+    let mut state = 0x1234_5678_9ABC_DEF0u64;
+    (0..size)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+fn run_test<D: Detector<T>, T>(name: &str, detector: D) {
+    const HAYSTACK_SIZE: usize = 4096;
+    const ROUNDS: usize = 10;
+    const TRIALS: usize = 20;
+
+    let haystack = haystack(HAYSTACK_SIZE);
+    let blocks: Vec<D::Block> = haystack.chunks_exact(size_of::<D::Block>()).map(D::from_slice).collect();
+
+    // Find all syncwords, as the syncword is found by chance in random bytes,
+    // and a search that stops at the first one would only time the beginning of the haystack.
+    // The fastest round is reported, as the slower ones are disturbed by other processes.
+    let fastest = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..TRIALS {
+                black_box(detector.positions_in_blocks(black_box(&blocks).iter().copied()).count());
+            }
+            start.elapsed()
+        })
+        .min()
+        .unwrap();
+    println!("{};{}", name, fastest.as_micros());
+}
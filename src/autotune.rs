@@ -0,0 +1,351 @@
+//! Select the fastest detector for a syncword and tolerance by timing the candidates on the running machine.
+//!
+//! ```
+//! use drone_framesync::autotune;
+//!
+//! let tuned = autotune::sync16::<0x9AC5>(2, 64).unwrap();
+//! println!("{}", tuned.report);
+//! let position = tuned.detector.find_in_bytes(&[0x00, 0x9A, 0xC5, 0x00]).map(|m| m.position);
+//! ```
+
+use std::{
+    boxed::Box,
+    fmt,
+    format,
+    hint::black_box,
+    mem::size_of,
+    string::String,
+    time::{Duration, Instant},
+    vec::Vec,
+};
+
+use crate::{
+    comparators::{
+        Exact16Comparator, Exact32Comparator, LeadingZeroCount16Comparator, LeadingZeroCount32Comparator,
+        PopCount16Comparator, PopCount32Comparator, TwosComplement16Comparator, TwosComplement32Comparator,
+    },
+    detectors::{
+        Bitap16Detector, Bitap32Detector, BitSliced16Detector, BitSliced32Detector, ByteTables16, ByteTables32, Detector,
+        Double16Detector, Double32Detector, DynByteDetector, LutDetector, NibbleTables16, NibbleTables32, Single16Detector,
+        Single32Detector,
+    },
+    lcg::Lcg,
+};
+
+#[cfg(target_arch = "x86_64")]
+use crate::detectors::{Simd16Detector, Simd32Detector};
+
+/// The shortest time that a candidate is timed, so that the measurement is not dominated by the clock resolution.
+const MIN_DURATION: Duration = Duration::from_millis(2);
+
+/// The timing of a candidate detector.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    /// The name of the candidate as listed by the benchmark project, e.g. `single16;popcnt2`.
+    pub name: String,
+    /// The detector type, e.g. `Single16Detector`.
+    pub detector: &'static str,
+    /// The comparator type for the syncword `SW`, e.g. `PopCount16Comparator::<SW, 2>`.
+    pub comparator: String,
+    /// The time to search one haystack.
+    pub duration: Duration,
+}
+
+/// The timings of all candidates for a syncword and tolerance, with the fastest first.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub syncword_bits: usize,
+    pub tolerance: usize,
+    pub haystack_size: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    /// The fastest candidate.
+    pub fn fastest(&self) -> &Measurement {
+        &self.measurements[0]
+    }
+}
+
+impl fmt::Display for Report {
+    /// List the candidates as the benchmark project does, with the time in nanoseconds.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for measurement in self.measurements.iter() {
+            writeln!(f, "{};{}", measurement.name, measurement.duration.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// The fastest detector and the timings it was selected from.
+pub struct Tuned<T> {
+    pub detector: Box<dyn DynByteDetector<T>>,
+    pub report: Report,
+}
+
+struct Candidate<T> {
+    measurement: Measurement,
+    detector: Box<dyn DynByteDetector<T>>,
+}
+
+fn candidate<D: Detector<T> + 'static, T>(
+    name: String,
+    detector_type: &'static str,
+    comparator: String,
+    detector: D,
+    haystack: &[u8],
+) -> Candidate<T> {
+    let duration = measure(&detector, haystack);
+    Candidate {
+        measurement: Measurement {
+            name,
+            detector: detector_type,
+            comparator,
+            duration,
+        },
+        detector: Box::new(detector),
+    }
+}
+
+/// Create a haystack of random bytes, i.e. where the syncword is only found by chance, as in the noise between frames.
+fn haystack(size: usize) -> Vec<u8> {
+    let mut rng = Lcg::default();
+    (0..size).map(|_| (rng.next_u64() >> 56) as u8).collect()
+}
+
+/// Get the time to search `haystack` once for all syncwords.
+/// The syncword is found by chance in random bytes, and a search that stops at the first one
+/// would only time the beginning of the haystack.
+fn measure<D: Detector<T>, T>(detector: &D, haystack: &[u8]) -> Duration {
+    let blocks: Vec<D::Block> = haystack.chunks_exact(size_of::<D::Block>()).map(D::from_slice).collect();
+
+    let mut trials = 1;
+    loop {
+        let start = Instant::now();
+        for _ in 0..trials {
+            black_box(detector.positions_in_blocks(black_box(&blocks).iter().copied()).count());
+        }
+        let elapsed = start.elapsed();
+
+        if elapsed >= MIN_DURATION {
+            return elapsed / trials;
+        }
+        trials *= 2;
+    }
+}
+
+/// Select the fastest of the timed candidates.
+fn tune<T>(mut candidates: Vec<Candidate<T>>, syncword_bits: usize, tolerance: usize, haystack_size: usize) -> Tuned<T> {
+    candidates.sort_by_key(|candidate| candidate.measurement.duration);
+
+    let measurements = candidates.iter().map(|candidate| candidate.measurement.clone()).collect();
+    Tuned {
+        detector: candidates.swap_remove(0).detector,
+        report: Report {
+            syncword_bits,
+            tolerance,
+            haystack_size,
+            measurements,
+        },
+    }
+}
+
+macro_rules! candidates {
+    (
+        $word:literal, $sw:ident, $tol:literal, $single:ident, $double:ident, $bitsliced:ident, $bitap:ident, $simd:ident,
+        $bytes:ident, $nibbles:ident, $exact:ident, $popcnt:ident, $twoscmpl:ident, $lzc:ident, $haystack:expr
+    ) => {{
+        let mut candidates = Vec::new();
+        for &(kind, detector_type) in [("single", stringify!($single)), ("double", stringify!($double))].iter() {
+            macro_rules! push {
+                ($short:expr, $comparator:ty, $comparator_type:expr) => {
+                    let name = format!(concat!("{}", $word, ";{}"), kind, $short);
+                    let comparator = $comparator_type;
+                    candidates.push(match kind {
+                        "single" => candidate(name, detector_type, comparator, $single::<$comparator>::new(), $haystack),
+                        _ => candidate(name, detector_type, comparator, $double::<$comparator>::new(), $haystack),
+                    });
+                };
+            }
+
+            if $tol == 0 {
+                push!("exact", $exact<$sw>, format!(concat!(stringify!($exact), "::<SW>")));
+            }
+            push!(concat!("popcnt", $tol), $popcnt<$sw, $tol>, format!(concat!(stringify!($popcnt), "::<SW, {}>"), $tol));
+            push!(concat!("twoscmpl", $tol), $twoscmpl<$sw, $tol>, format!(concat!(stringify!($twoscmpl), "::<SW, {}>"), $tol));
+            push!(concat!("lzc", $tol), $lzc<$sw, $tol>, format!(concat!(stringify!($lzc), "::<SW, {}>"), $tol));
+        }
+
+        // The detectors that count the differing bits themselves only take a Hamming comparator.
+        macro_rules! push_hamming {
+            ($kind:literal, $detector:ident) => {
+                candidates.push(candidate(
+                    format!(concat!($kind, $word, ";popcnt{}"), $tol),
+                    stringify!($detector),
+                    format!(concat!(stringify!($popcnt), "::<SW, {}>"), $tol),
+                    $detector::<$popcnt<$sw, $tol>>::new(),
+                    $haystack,
+                ));
+            };
+        }
+        push_hamming!("bitsliced", $bitsliced);
+        push_hamming!("bitap", $bitap);
+        #[cfg(target_arch = "x86_64")]
+        push_hamming!("simd", $simd);
+
+        // The lookup tables take the threshold instead of a comparator, see the `LutDetector` arm of `impl_sync!`.
+        macro_rules! push_lut {
+            ($short:literal, $tables:ident) => {
+                candidates.push(candidate(
+                    format!(concat!("lut", $word, ";", $short, "{}"), $tol),
+                    "LutDetector",
+                    String::from(concat!(stringify!($tables), "::<SW>")),
+                    LutDetector::<$tables<$sw>>::new($tol),
+                    $haystack,
+                ));
+            };
+        }
+        push_lut!("byte", $bytes);
+        push_lut!("nibble", $nibbles);
+
+        candidates
+    }};
+}
+
+macro_rules! impl_autotune {
+    (
+        $name:ident, $table:ident, $word:ty, $bits:literal, $single:ident, $double:ident, $bitsliced:ident, $bitap:ident,
+        $simd:ident, $bytes:ident, $nibbles:ident, $exact:ident, $popcnt:ident, $twoscmpl:ident, $lzc:ident, $($tol:literal),*
+    ) => {
+        /// Time the candidate detectors for the syncword `SW` with `tolerance` bit errors
+        /// on a random haystack of `haystack_size` bytes, and return the fastest, or `None` for an unsupported tolerance.
+        pub fn $name<const SW: $word>(tolerance: usize, haystack_size: usize) -> Option<Tuned<$word>> {
+            let haystack = haystack(haystack_size);
+            match tolerance {
+                $(
+                    $tol => Some(tune(
+                        candidates!(
+                            $bits, SW, $tol, $single, $double, $bitsliced, $bitap, $simd, $bytes, $nibbles, $exact, $popcnt,
+                            $twoscmpl, $lzc, &haystack
+                        ),
+                        $bits,
+                        tolerance,
+                        haystack_size,
+                    )),
+                )*
+                _ => None,
+            }
+        }
+
+        /// Tune all tolerances for the syncword `SW` and emit the Rust source for a selection table,
+        /// in the format of e.g. `detectors::cortexm0`, preceded by the timings as comments.
+        pub fn $table<const SW: $word>(haystack_size: usize) -> String {
+            let mut output = format!("// Output from autotune with a haystack of {} bytes in nanoseconds\n", haystack_size);
+            let mut selection = String::new();
+
+            $(
+                let report = $name::<SW>($tol, haystack_size).unwrap().report;
+                for measurement in report.measurements.iter() {
+                    output += &format!("// {};{}\n", measurement.name, measurement.duration.as_nanos());
+                }
+
+                let fastest = report.fastest();
+                selection += &format!(
+                    concat!("impl_sync!(sync", $bits, "_tol{}<", stringify!($word), ">, Select", $bits, "<{}>, {}, {});\n"),
+                    $tol, $tol, fastest.detector, fastest.comparator
+                );
+            )*
+
+            output + "\n" + &selection
+        }
+    };
+}
+
+impl_autotune!(
+    sync16,
+    table16,
+    u16,
+    16,
+    Single16Detector,
+    Double16Detector,
+    BitSliced16Detector,
+    Bitap16Detector,
+    Simd16Detector,
+    ByteTables16,
+    NibbleTables16,
+    Exact16Comparator,
+    PopCount16Comparator,
+    TwosComplement16Comparator,
+    LeadingZeroCount16Comparator,
+    0, 1, 2, 3, 4
+);
+impl_autotune!(
+    sync32,
+    table32,
+    u32,
+    32,
+    Single32Detector,
+    Double32Detector,
+    BitSliced32Detector,
+    Bitap32Detector,
+    Simd32Detector,
+    ByteTables32,
+    NibbleTables32,
+    Exact32Comparator,
+    PopCount32Comparator,
+    TwosComplement32Comparator,
+    LeadingZeroCount32Comparator,
+    0, 1, 2, 3, 4, 5, 6
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fastest_detector() {
+        let tuned = sync16::<0x9AC5>(2, 16).unwrap();
+
+        // Single and double detectors with the popcount, twos complement and leading zero count comparators,
+        // the bit-sliced and bitap detectors, the byte and nibble tables, and the SIMD detector on x86_64.
+        let expected = if cfg!(target_arch = "x86_64") { 11 } else { 10 };
+        assert_eq!(expected, tuned.report.measurements.len());
+        assert!(tuned
+            .report
+            .measurements
+            .windows(2)
+            .all(|pair| pair[0].duration <= pair[1].duration));
+
+        let haystack = [0x00, 0x9A, 0xC4, 0x00];
+        assert_eq!(Some(8), tuned.detector.find_in_bytes(&haystack).map(|m| m.position));
+        assert!(sync16::<0x9AC5>(5, 16).is_none());
+    }
+
+    #[test]
+    fn table_source() {
+        let source = table16::<0x9AC5>(16);
+
+        assert!(source.contains("// single16;exact;"));
+        assert!(source.contains("// bitsliced16;popcnt2;"));
+        assert!(source.contains("// lut16;nibble4;"));
+        for tolerance in 0..=4 {
+            let prefix = format!("impl_sync!(sync16_tol{}<u16>, Select16<{}>, ", tolerance, tolerance);
+            assert!(source.lines().any(|line| line.starts_with(&prefix)));
+        }
+    }
+
+    /// A table where the lookup tables are the fastest, as emitted by `table16`.
+    mod lut_table {
+        use crate::detectors::{select::impl_sync, ByteTables16};
+
+        pub struct Table;
+
+        impl_sync!(sync16_tol1<u16>, Select16<1>, LutDetector, ByteTables16::<SW>);
+    }
+
+    #[test]
+    fn lut_selection() {
+        let haystack = [0x00, 0x9A, 0xC4, 0x00];
+        assert_eq!(Some(8), lut_table::sync16_tol1::<0x9AC5>().position(&haystack));
+    }
+}
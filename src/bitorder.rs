@@ -0,0 +1,94 @@
+/// The order in which the bits of each byte in a haystack are received.
+/// Detectors load their blocks with the bit order such that the first received bit is the most significant bit,
+/// i.e. the syncword is always specified with its first received bit as the most significant bit.
+pub trait BitOrder: Copy {
+    /// Whether the least significant bit of each byte is received first.
+    const LSB_FIRST: bool;
+
+    fn load8(block: u8) -> u8;
+    fn load16(block: u16) -> u16;
+    fn load32(block: u32) -> u32;
+    fn load64(block: u64) -> u64;
+    fn load128(block: u128) -> u128;
+}
+
+/// The most significant bit of each byte is received first.
+#[derive(Clone, Copy, Default)]
+pub struct MsbFirst;
+
+/// The least significant bit of each byte is received first.
+#[derive(Clone, Copy, Default)]
+pub struct LsbFirst;
+
+impl BitOrder for MsbFirst {
+    const LSB_FIRST: bool = false;
+
+    #[inline(always)]
+    fn load8(block: u8) -> u8 {
+        block
+    }
+
+    #[inline(always)]
+    fn load16(block: u16) -> u16 {
+        u16::from_be(block)
+    }
+
+    #[inline(always)]
+    fn load32(block: u32) -> u32 {
+        u32::from_be(block)
+    }
+
+    #[inline(always)]
+    fn load64(block: u64) -> u64 {
+        u64::from_be(block)
+    }
+
+    #[inline(always)]
+    fn load128(block: u128) -> u128 {
+        u128::from_be(block)
+    }
+}
+
+// Reversing all bits of the little endian value reverses the bits within each byte,
+// while keeping the first byte as the most significant byte.
+impl BitOrder for LsbFirst {
+    const LSB_FIRST: bool = true;
+
+    #[inline(always)]
+    fn load8(block: u8) -> u8 {
+        block.reverse_bits()
+    }
+
+    #[inline(always)]
+    fn load16(block: u16) -> u16 {
+        u16::from_le(block).reverse_bits()
+    }
+
+    #[inline(always)]
+    fn load32(block: u32) -> u32 {
+        u32::from_le(block).reverse_bits()
+    }
+
+    #[inline(always)]
+    fn load64(block: u64) -> u64 {
+        u64::from_le(block).reverse_bits()
+    }
+
+    #[inline(always)]
+    fn load128(block: u128) -> u128 {
+        u128::from_le(block).reverse_bits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load() {
+        let block = u32::from_ne_bytes([0x01, 0x80, 0x03, 0xF0]);
+
+        assert_eq!(0x0180_03F0, MsbFirst::load32(block));
+        assert_eq!(0x8001_C00F, LsbFirst::load32(block));
+    }
+}
@@ -1,14 +1,21 @@
 use super::Comparator;
 
+#[derive(Clone, Copy, Default)]
 pub struct LeadingZeroCount16Comparator<const SW: u16, const THR: usize>;
+#[derive(Clone, Copy, Default)]
 pub struct LeadingZeroCount32Comparator<const SW: u32, const THR: usize>;
+#[derive(Clone, Copy, Default)]
+pub struct LeadingZeroCount64Comparator<const SW: u64, const THR: usize>;
 
 impl<const SW: u16, const THR: usize> Comparator<u16> for LeadingZeroCount16Comparator<SW, THR> {
-    const SYNCWORD: u16 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW
+    }
 
     #[inline(always)]
-    fn is_match(value: u16) -> bool {
-        let mut r = value ^ Self::SYNCWORD;
+    fn is_match(&self, value: u16) -> bool {
+        let mut r = value ^ self.syncword();
 
         for _ in 0..THR {
             r = r.overflowing_shl(r.leading_zeros()).0;
@@ -17,14 +24,27 @@ impl<const SW: u16, const THR: usize> Comparator<u16> for LeadingZeroCount16Comp
 
         r == 0
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword()
+    }
 }
 
 impl<const SW: u32, const THR: usize> Comparator<u32> for LeadingZeroCount32Comparator<SW, THR> {
-    const SYNCWORD: u32 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW
+    }
 
     #[inline(always)]
-    fn is_match(value: u32) -> bool {
-        let mut r = value ^ Self::SYNCWORD;
+    fn is_match(&self, value: u32) -> bool {
+        let mut r = value ^ self.syncword();
 
         for _ in 0..THR {
             r = r.overflowing_shl(r.leading_zeros()).0;
@@ -33,6 +53,45 @@ impl<const SW: u32, const THR: usize> Comparator<u32> for LeadingZeroCount32Comp
 
         r == 0
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const SW: u64, const THR: usize> Comparator<u64> for LeadingZeroCount64Comparator<SW, THR> {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        let mut r = value ^ self.syncword();
+
+        for _ in 0..THR {
+            r = r.overflowing_shl(r.leading_zeros()).0;
+            r &= !0x8000_0000_0000_0000;
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword()
+    }
 }
 
 #[cfg(test)]
@@ -43,25 +102,56 @@ pub mod tests {
 
     #[test]
     fn is_match_16() {
-        assert!(LeadingZeroCount16Comparator::<0xFFFF, 1>::is_match(0xFFFF));
-        assert!(LeadingZeroCount16Comparator::<0xFFFF, 1>::is_match(0xFFFE));
-        assert!(!LeadingZeroCount16Comparator::<0xFFFF, 1>::is_match(0xFFFC));
+        assert!(LeadingZeroCount16Comparator::<0xFFFF, 1>.is_match(0xFFFF));
+        assert!(LeadingZeroCount16Comparator::<0xFFFF, 1>.is_match(0xFFFE));
+        assert!(!LeadingZeroCount16Comparator::<0xFFFF, 1>.is_match(0xFFFC));
 
-        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>::is_match(0xFFFF));
-        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>::is_match(0xFFFE));
-        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>::is_match(0xFFFC));
-        assert!(!LeadingZeroCount16Comparator::<0xFFFF, 2>::is_match(0xFFF8));
+        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>.is_match(0xFFFF));
+        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>.is_match(0xFFFE));
+        assert!(LeadingZeroCount16Comparator::<0xFFFF, 2>.is_match(0xFFFC));
+        assert!(!LeadingZeroCount16Comparator::<0xFFFF, 2>.is_match(0xFFF8));
     }
 
     #[test]
     fn is_match_32() {
-        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFF_FFFF));
-        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFE_FFFF));
-        assert!(!LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFC_FFFF));
-
-        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFF_FFFF));
-        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFE_FFFF));
-        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFC_FFFF));
-        assert!(!LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFF8_FFFF));
+        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFF_FFFF));
+        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFE_FFFF));
+        assert!(!LeadingZeroCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFC_FFFF));
+
+        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFF_FFFF));
+        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFE_FFFF));
+        assert!(LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFC_FFFF));
+        assert!(!LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFF8_FFFF));
+    }
+
+    #[test]
+    fn is_match_64() {
+        assert!(LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(!LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+
+        assert!(LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+        assert!(!LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFF8_FFFF_FFFF));
+        assert!(!LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0x7FFF_FFFF_FFFF_FFFC));
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(0, LeadingZeroCount16Comparator::<0xFFFF, 2>.distance(0xFFFF));
+        assert_eq!(1, LeadingZeroCount16Comparator::<0xFFFF, 2>.distance(0xFFFE));
+        assert_eq!(3, LeadingZeroCount16Comparator::<0xFFFF, 2>.distance(0x7FFC));
+        assert_eq!(0x8003, LeadingZeroCount16Comparator::<0xFFFF, 2>.error_mask(0x7FFC));
+
+        assert_eq!(0, LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFF_FFFF));
+        assert_eq!(1, LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFE_FFFF));
+        assert_eq!(3, LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.distance(0x7FFC_FFFF));
+        assert_eq!(0x8003_0000, LeadingZeroCount32Comparator::<0xFFFFFFFF, 2>.error_mask(0x7FFC_FFFF));
+
+        assert_eq!(0, LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFF_FFFF_FFFF));
+        assert_eq!(1, LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFE_FFFF_FFFF));
+        assert_eq!(3, LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0x7FFF_FFFF_FFFF_FFFC));
+        assert_eq!(0x8000_0000_0000_0003, LeadingZeroCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.error_mask(0x7FFF_FFFF_FFFF_FFFC));
     }
 }
@@ -1,37 +1,430 @@
 pub(crate) mod lzc;
+pub(crate) mod multi;
+pub(crate) mod polarity;
 pub(crate) mod popcnt;
+pub(crate) mod swpopcnt;
 pub(crate) mod twoscmpl;
+pub(crate) mod weighted;
+
+use core::mem::size_of;
+
+/// Check the parameters of a `*BitsComparator` when it is used, and return the number of syncword bits.
+/// `BITS` must be 1 to 128, and the syncword must fit in `BITS` bits, as it is compared with the top `BITS` bits of a value.
+pub(crate) const fn syncword_bits(bits: usize, syncword: u128) -> usize {
+    assert!(bits >= 1 && bits <= 128, "BITS must be 1 to 128");
+    assert!(bits == 128 || syncword >> bits == 0, "The syncword must fit in BITS bits");
+    bits
+}
 
 pub trait Comparator<T> {
-    const SYNCWORD: T;
+    /// The number of bits in the syncword.
+    const SYNCWORD_BITS: usize = 8 * size_of::<T>();
+
+    /// Get the syncword.
+    fn syncword(&self) -> T;
+
+    /// Determines if `value` is sufficently similar to the syncword.
+    fn is_match(&self, value: T) -> bool;
+
+    /// Get the number of bits in `value` that differ from the syncword.
+    fn distance(&self, value: T) -> u32;
+
+    /// Get the bits in `value` that differ from the syncword.
+    fn error_mask(&self, value: T) -> T;
+
+    /// Get the index of the syncword that `value` matches, for comparators with several syncwords.
+    fn syncword_index(&self, _value: T) -> usize {
+        0
+    }
+
+    /// Determines if `value` matches the bitwise complement of the syncword, for comparators that accept both polarities.
+    fn is_inverted(&self, _value: T) -> bool {
+        false
+    }
+}
 
-    /// Determines if `value` is sufficently similar to `SYNCWORD`.
-    fn is_match(value: T) -> bool;
+/// A comparator with its syncword and threshold in the type, so that the detectors can create it in a `const fn`,
+/// e.g. `Single16Detector::new()` in a `static`.
+pub trait ConstDefault {
+    const DEFAULT: Self;
 }
 
+macro_rules! impl_const_default {
+    ($($name:ident<$(const $param:ident: $type:ty),*>),* $(,)?) => {
+        $(
+            impl<$(const $param: $type),*> ConstDefault for $name<$($param),*> {
+                const DEFAULT: Self = Self;
+            }
+        )*
+    };
+}
+
+impl_const_default!(
+    Exact8Comparator<const SW: u8>,
+    Exact16Comparator<const SW: u16>,
+    Exact32Comparator<const SW: u32>,
+    Exact64Comparator<const SW: u64>,
+    ExactBitsComparator<const BITS: usize, const SW: u128>,
+    MaskedExact16Comparator<const SW: u16, const MASK: u16>,
+    MaskedExact32Comparator<const SW: u32, const MASK: u32>,
+    LeadingZeroCount16Comparator<const SW: u16, const THR: usize>,
+    LeadingZeroCount32Comparator<const SW: u32, const THR: usize>,
+    LeadingZeroCount64Comparator<const SW: u64, const THR: usize>,
+    PopCount8Comparator<const SW: u8, const THR: u32>,
+    PopCount16Comparator<const SW: u16, const THR: u32>,
+    PopCount32Comparator<const SW: u32, const THR: u32>,
+    PopCount64Comparator<const SW: u64, const THR: u32>,
+    PopCountBitsComparator<const BITS: usize, const SW: u128, const THR: u32>,
+    MaskedPopCount16Comparator<const SW: u16, const MASK: u16, const THR: u32>,
+    MaskedPopCount32Comparator<const SW: u32, const MASK: u32, const THR: u32>,
+    TwosComplement16Comparator<const SW: u16, const THR: usize>,
+    TwosComplement32Comparator<const SW: u32, const THR: usize>,
+    TwosComplement64Comparator<const SW: u64, const THR: usize>,
+    TwosComplementBitsComparator<const BITS: usize, const SW: u128, const THR: usize>,
+    MaskedTwosComplement16Comparator<const SW: u16, const MASK: u16, const THR: usize>,
+    MaskedTwosComplement32Comparator<const SW: u32, const MASK: u32, const THR: usize>,
+    LutPopCount16Comparator<const SW: u16, const THR: u32>,
+    LutPopCount32Comparator<const SW: u32, const THR: u32>,
+    SwarPopCount16Comparator<const SW: u16, const THR: u32>,
+    SwarPopCount32Comparator<const SW: u32, const THR: u32>,
+    EarlyExitPopCount16Comparator<const SW: u16, const THR: u32>,
+    EarlyExitPopCount32Comparator<const SW: u32, const THR: u32>,
+);
+
+#[derive(Clone, Copy, Default)]
+pub struct Exact8Comparator<const SW: u8>;
+#[derive(Clone, Copy, Default)]
 pub struct Exact16Comparator<const SW: u16>;
+#[derive(Clone, Copy, Default)]
 pub struct Exact32Comparator<const SW: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct Exact64Comparator<const SW: u64>;
+
+/// Comparator for a `BITS` bit syncword, 1 to 128 bits, that is compared with the top `BITS` bits of a 128 bit value.
+///
+/// ```
+/// use drone_framesync::comparators::{Comparator, ExactBitsComparator};
+///
+/// assert!(ExactBitsComparator::<12, 0xABC>.is_match(0xABC << 116));
+/// ```
+///
+/// A syncword with more than `BITS` bits fails to compile:
+///
+/// ```compile_fail
+/// use drone_framesync::comparators::{Comparator, ExactBitsComparator};
+///
+/// ExactBitsComparator::<12, 0x1ABC>.is_match(0);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct ExactBitsComparator<const BITS: usize, const SW: u128>;
+
+/// Comparator for a syncword that is configured at runtime.
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeExact16Comparator {
+    pub syncword: u16,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeExact32Comparator {
+    pub syncword: u32,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeExact64Comparator {
+    pub syncword: u64,
+}
+
+/// Comparator for a syncword where only the bits set in `MASK` are compared.
+#[derive(Clone, Copy, Default)]
+pub struct MaskedExact16Comparator<const SW: u16, const MASK: u16>;
+#[derive(Clone, Copy, Default)]
+pub struct MaskedExact32Comparator<const SW: u32, const MASK: u32>;
+
+impl<const SW: u8> Comparator<u8> for Exact8Comparator<SW> {
+    #[inline(always)]
+    fn syncword(&self) -> u8 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u8) -> bool {
+        value == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u8) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u8) -> u8 {
+        value ^ self.syncword()
+    }
+}
 
 impl<const SW: u16> Comparator<u16> for Exact16Comparator<SW> {
-    const SYNCWORD: u16 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        value == self.syncword()
+    }
 
     #[inline(always)]
-    fn is_match(value: u16) -> bool {
-        value == Self::SYNCWORD
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword()
     }
 }
 
 impl<const SW: u32> Comparator<u32> for Exact32Comparator<SW> {
-    const SYNCWORD: u32 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        value == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const SW: u64> Comparator<u64> for Exact64Comparator<SW> {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        value == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const BITS: usize, const SW: u128> Comparator<u128> for ExactBitsComparator<BITS, SW> {
+    const SYNCWORD_BITS: usize = syncword_bits(BITS, SW);
+
+    #[inline(always)]
+    fn syncword(&self) -> u128 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u128) -> bool {
+        value >> (128 - Self::SYNCWORD_BITS) == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u128) -> u32 {
+        self.error_mask(value).count_ones()
+    }
 
     #[inline(always)]
-    fn is_match(value: u32) -> bool {
-        value == Self::SYNCWORD
+    fn error_mask(&self, value: u128) -> u128 {
+        (value >> (128 - Self::SYNCWORD_BITS)) ^ self.syncword()
+    }
+}
+
+impl<const SW: u16, const MASK: u16> Comparator<u16>
+    for MaskedExact16Comparator<SW, MASK>
+{
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW & MASK
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        (value & MASK) == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        (value ^ self.syncword()) & MASK
+    }
+}
+
+impl<const SW: u32, const MASK: u32> Comparator<u32>
+    for MaskedExact32Comparator<SW, MASK>
+{
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW & MASK
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        (value & MASK) == self.syncword()
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        (value ^ self.syncword()) & MASK
+    }
+}
+
+impl Comparator<u16> for RuntimeExact16Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        value == self.syncword
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword
+    }
+}
+
+impl Comparator<u32> for RuntimeExact32Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        value == self.syncword
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword
+    }
+}
+
+impl Comparator<u64> for RuntimeExact64Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        value == self.syncword
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword
     }
 }
 
 pub use self::{
-    lzc::LeadingZeroCount16Comparator, lzc::LeadingZeroCount32Comparator,
-    popcnt::PopCount16Comparator, popcnt::PopCount32Comparator,
-    twoscmpl::TwosComplement16Comparator, twoscmpl::TwosComplement32Comparator,
+    lzc::{
+        LeadingZeroCount16Comparator, LeadingZeroCount32Comparator, LeadingZeroCount64Comparator,
+    },
+    multi::MultiComparator,
+    polarity::AnyPolarityComparator,
+    popcnt::{
+        HammingComparator, MaskedPopCount16Comparator, MaskedPopCount32Comparator,
+        PopCount16Comparator, PopCount32Comparator, PopCount64Comparator, PopCount8Comparator,
+        PopCountBitsComparator, RuntimePopCount16Comparator, RuntimePopCount32Comparator,
+        RuntimePopCount64Comparator,
+    },
+    swpopcnt::{
+        EarlyExitPopCount16Comparator, EarlyExitPopCount32Comparator, LutPopCount16Comparator,
+        LutPopCount32Comparator, SwarPopCount16Comparator, SwarPopCount32Comparator,
+    },
+    twoscmpl::{
+        MaskedTwosComplement16Comparator, MaskedTwosComplement32Comparator,
+        RuntimeTwosComplement16Comparator, RuntimeTwosComplement32Comparator,
+        RuntimeTwosComplement64Comparator, TwosComplement16Comparator, TwosComplement32Comparator,
+        TwosComplement64Comparator, TwosComplementBitsComparator,
+    },
+    weighted::{Weighted16Comparator, Weighted32Comparator},
 };
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn is_match_masked() {
+        assert!(MaskedExact16Comparator::<0xFFFF, 0xF0FF>.is_match(0xFFFF));
+        assert!(MaskedExact16Comparator::<0xFFFF, 0xF0FF>.is_match(0xF5FF));
+        assert!(!MaskedExact16Comparator::<0xFFFF, 0xF0FF>.is_match(0xF5FE));
+
+        assert!(MaskedExact32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0>.is_match(0xFFFF_FFF0));
+        assert!(!MaskedExact32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0>.is_match(0x7FFF_FFF0));
+    }
+
+    #[test]
+    fn is_match_runtime() {
+        let comparator = RuntimeExact16Comparator { syncword: 0xF0F0 };
+        assert!(comparator.is_match(0xF0F0));
+        assert!(!comparator.is_match(0xF0F1));
+        assert_eq!(0x0001, comparator.error_mask(0xF0F1));
+
+        let comparator = RuntimeExact32Comparator { syncword: 0xF0F0_1234 };
+        assert!(comparator.is_match(0xF0F0_1234));
+        assert!(!comparator.is_match(0xF0F0_1235));
+
+        let comparator = RuntimeExact64Comparator { syncword: 0xF0F0_1234_5678_9ABC };
+        assert!(comparator.is_match(0xF0F0_1234_5678_9ABC));
+        assert!(!comparator.is_match(0x70F0_1234_5678_9ABC));
+    }
+
+    #[test]
+    fn distance_masked() {
+        assert_eq!(0, MaskedExact16Comparator::<0xFFFF, 0xF0FF>.distance(0xF0FF));
+        assert_eq!(2, MaskedExact16Comparator::<0xFFFF, 0xF0FF>.distance(0x70FE));
+        assert_eq!(0x8001, MaskedExact16Comparator::<0xFFFF, 0xF0FF>.error_mask(0x70FE));
+
+        assert_eq!(0x0000_0010, MaskedExact32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0>.error_mask(0xFFFF_FFE0));
+    }
+}
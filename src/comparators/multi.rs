@@ -0,0 +1,102 @@
+use super::Comparator;
+
+/// Comparator for a small set of syncwords, each given by its own comparator and thereby its own tolerance.
+/// All syncwords are compared in the same pass over the shift window,
+/// and the index of the syncword that matched is reported in `Match::syncword_index`.
+/// If several syncwords match, the one with the lowest index is reported.
+/// The comparators are kept in an array, so they must all be of the same type,
+/// i.e. in practice runtime comparators such as `RuntimePopCount32Comparator`, where each has its own syncword and threshold.
+#[derive(Clone, Copy)]
+pub struct MultiComparator<C, const N: usize> {
+    comparators: [C; N],
+}
+
+impl<C, const N: usize> MultiComparator<C, N> {
+    pub const fn new(comparators: [C; N]) -> Self {
+        Self { comparators }
+    }
+}
+
+impl<T: Copy, C: Comparator<T>, const N: usize> Comparator<T> for MultiComparator<C, N> {
+    const SYNCWORD_BITS: usize = C::SYNCWORD_BITS;
+
+    /// Get the first syncword.
+    #[inline(always)]
+    fn syncword(&self) -> T {
+        self.comparators[0].syncword()
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: T) -> bool {
+        self.comparators.iter().any(|c| c.is_match(value))
+    }
+
+    #[inline(always)]
+    fn syncword_index(&self, value: T) -> usize {
+        self.comparators
+            .iter()
+            .position(|c| c.is_match(value))
+            .unwrap_or(0)
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: T) -> u32 {
+        self.comparators[self.syncword_index(value)].distance(value)
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: T) -> T {
+        self.comparators[self.syncword_index(value)].error_mask(value)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::comparators::{Comparator, RuntimePopCount32Comparator};
+
+    use super::*;
+
+    const FORMAT_A: u32 = 0x543D_54CD;
+    const FORMAT_B: u32 = 0x543D_543D;
+
+    #[test]
+    fn is_match() {
+        let comparator = MultiComparator::new([
+            RuntimePopCount32Comparator {
+                syncword: FORMAT_A,
+                threshold: 0,
+            },
+            RuntimePopCount32Comparator {
+                syncword: FORMAT_B,
+                threshold: 2,
+            },
+        ]);
+
+        assert!(comparator.is_match(FORMAT_A));
+        assert!(!comparator.is_match(FORMAT_A ^ 0x0001));
+        assert!(comparator.is_match(FORMAT_B));
+        assert!(comparator.is_match(FORMAT_B ^ 0x0003));
+        assert!(!comparator.is_match(FORMAT_B ^ 0x0007));
+    }
+
+    #[test]
+    fn syncword_index() {
+        let comparator = MultiComparator::new([
+            RuntimePopCount32Comparator {
+                syncword: FORMAT_A,
+                threshold: 1,
+            },
+            RuntimePopCount32Comparator {
+                syncword: FORMAT_B,
+                threshold: 1,
+            },
+        ]);
+
+        assert_eq!(0, comparator.syncword_index(FORMAT_A));
+        assert_eq!(1, comparator.syncword_index(FORMAT_B));
+        assert_eq!(1, comparator.syncword_index(FORMAT_B ^ 0x8000_0000));
+
+        assert_eq!(1, comparator.distance(FORMAT_B ^ 0x8000_0000));
+        assert_eq!(0x8000_0000, comparator.error_mask(FORMAT_B ^ 0x8000_0000));
+    }
+}
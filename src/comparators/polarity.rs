@@ -0,0 +1,108 @@
+use core::ops::Not;
+
+use super::{Comparator, ConstDefault};
+
+/// Comparator that matches both the syncword of the wrapped comparator and its bitwise complement,
+/// e.g. for a bitstream that is received inverted because of a phase ambiguity.
+/// The polarity that was found is reported in `Match::inverted`.
+#[derive(Clone, Copy, Default)]
+pub struct AnyPolarityComparator<C> {
+    comparator: C,
+}
+
+impl<C> AnyPolarityComparator<C> {
+    pub const fn new(comparator: C) -> Self {
+        Self { comparator }
+    }
+}
+
+impl<C: ConstDefault> ConstDefault for AnyPolarityComparator<C> {
+    const DEFAULT: Self = Self::new(C::DEFAULT);
+}
+
+impl<T: Copy + Not<Output = T>, C: Comparator<T>> Comparator<T> for AnyPolarityComparator<C> {
+    const SYNCWORD_BITS: usize = C::SYNCWORD_BITS;
+
+    #[inline(always)]
+    fn syncword(&self) -> T {
+        self.comparator.syncword()
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: T) -> bool {
+        self.comparator.is_match(value) || self.comparator.is_match(!value)
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: T) -> u32 {
+        self.comparator.distance(self.normalize(value))
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: T) -> T {
+        self.comparator.error_mask(self.normalize(value))
+    }
+
+    #[inline(always)]
+    fn syncword_index(&self, value: T) -> usize {
+        self.comparator.syncword_index(self.normalize(value))
+    }
+
+    #[inline(always)]
+    fn is_inverted(&self, value: T) -> bool {
+        !self.comparator.is_match(value)
+    }
+}
+
+impl<C> AnyPolarityComparator<C> {
+    /// Get `value` in the polarity of the syncword.
+    #[inline(always)]
+    fn normalize<T: Copy + Not<Output = T>>(&self, value: T) -> T
+    where
+        C: Comparator<T>,
+    {
+        if self.comparator.is_match(value) {
+            value
+        } else {
+            !value
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::comparators::{Comparator, PopCount16Comparator, TwosComplement32Comparator};
+
+    use super::*;
+
+    #[test]
+    fn is_match() {
+        let comparator = AnyPolarityComparator::<PopCount16Comparator<0xF0F0, 1>>::default();
+
+        assert!(comparator.is_match(0xF0F0));
+        assert!(comparator.is_match(0xF0F1));
+        assert!(comparator.is_match(0x0F0F));
+        assert!(comparator.is_match(0x0F0E));
+        assert!(!comparator.is_match(0x0F0C));
+        assert!(!comparator.is_match(0xFFFF));
+
+        let comparator = AnyPolarityComparator::<TwosComplement32Comparator<0x1234_5678, 2>>::default();
+
+        assert!(comparator.is_match(0x1234_5678));
+        assert!(comparator.is_match(!0x1234_5678));
+        assert!(!comparator.is_match(!0x1234_5678 ^ 0x0000_0007));
+    }
+
+    #[test]
+    fn is_inverted() {
+        let comparator = AnyPolarityComparator::<PopCount16Comparator<0xF0F0, 1>>::default();
+
+        assert!(!comparator.is_inverted(0xF0F1));
+        assert_eq!(1, comparator.distance(0xF0F1));
+        assert_eq!(0x0001, comparator.error_mask(0xF0F1));
+
+        assert!(comparator.is_inverted(0x0F0E));
+        assert_eq!(1, comparator.distance(0x0F0E));
+        assert_eq!(0x0001, comparator.error_mask(0x0F0E));
+    }
+}
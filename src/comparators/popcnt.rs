@@ -1,30 +1,352 @@
-use super::Comparator;
+use super::{syncword_bits, Comparator};
 
+#[derive(Clone, Copy, Default)]
+pub struct PopCount8Comparator<const SW: u8, const THR: u32>;
+#[derive(Clone, Copy, Default)]
 pub struct PopCount16Comparator<const SW: u16, const THR: u32>;
+#[derive(Clone, Copy, Default)]
 pub struct PopCount32Comparator<const SW: u32, const THR: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct PopCount64Comparator<const SW: u64, const THR: u32>;
+
+/// Comparator for a `BITS` bit syncword, 1 to 128 bits, that is compared with the top `BITS` bits of a 128 bit value.
+#[derive(Clone, Copy, Default)]
+pub struct PopCountBitsComparator<const BITS: usize, const SW: u128, const THR: u32>;
+
+/// Comparator for a syncword and threshold that are configured at runtime.
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimePopCount16Comparator {
+    pub syncword: u16,
+    pub threshold: u32,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimePopCount32Comparator {
+    pub syncword: u32,
+    pub threshold: u32,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimePopCount64Comparator {
+    pub syncword: u64,
+    pub threshold: u32,
+}
+
+/// Comparator for a syncword where only the bits set in `MASK` are compared,
+/// and `THR` is the number of compared bits that may differ.
+#[derive(Clone, Copy, Default)]
+pub struct MaskedPopCount16Comparator<const SW: u16, const MASK: u16, const THR: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct MaskedPopCount32Comparator<const SW: u32, const MASK: u32, const THR: u32>;
+
+/// Comparator that accepts values where at most `threshold()` bits differ from the syncword,
+/// so that detectors can compute the Hamming distances themselves, e.g. for many offsets in parallel.
+pub trait HammingComparator<T>: Comparator<T> {
+    /// Get the number of bits that may differ.
+    fn threshold(&self) -> u32;
+
+    /// Get the syncword bits that are compared, i.e. all bits except for the masked comparators.
+    fn mask(&self) -> T;
+}
+
+impl<const SW: u8, const THR: u32> Comparator<u8> for PopCount8Comparator<SW, THR> {
+    #[inline(always)]
+    fn syncword(&self) -> u8 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u8) -> bool {
+        let r = value ^ self.syncword();
+
+        r.count_ones() <= THR
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u8) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u8) -> u8 {
+        value ^ self.syncword()
+    }
+}
 
 impl<const SW: u16, const THR: u32> Comparator<u16> for PopCount16Comparator<SW, THR> {
-    const SYNCWORD: u16 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW
+    }
 
     #[inline(always)]
-    fn is_match(value: u16) -> bool {
-        let r = value ^ Self::SYNCWORD;
+    fn is_match(&self, value: u16) -> bool {
+        let r = value ^ self.syncword();
 
         r.count_ones() <= THR
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword()
+    }
 }
 
 impl<const SW: u32, const THR: u32> Comparator<u32> for PopCount32Comparator<SW, THR> {
-    const SYNCWORD: u32 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        let r = value ^ self.syncword();
+
+        r.count_ones() <= THR
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const SW: u64, const THR: u32> Comparator<u64> for PopCount64Comparator<SW, THR> {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        let r = value ^ self.syncword();
+
+        r.count_ones() <= THR
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const BITS: usize, const SW: u128, const THR: u32> Comparator<u128>
+    for PopCountBitsComparator<BITS, SW, THR>
+{
+    const SYNCWORD_BITS: usize = syncword_bits(BITS, SW);
+
+    #[inline(always)]
+    fn syncword(&self) -> u128 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u128) -> bool {
+        let r = (value >> (128 - Self::SYNCWORD_BITS)) ^ self.syncword();
+
+        r.count_ones() <= THR
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u128) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u128) -> u128 {
+        (value >> (128 - Self::SYNCWORD_BITS)) ^ self.syncword()
+    }
+}
+
+impl<const SW: u16, const MASK: u16, const THR: u32> Comparator<u16>
+    for MaskedPopCount16Comparator<SW, MASK, THR>
+{
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW & MASK
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        let r = (value ^ self.syncword()) & MASK;
+
+        r.count_ones() <= THR
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        (value ^ self.syncword()) & MASK
+    }
+}
 
+impl<const SW: u32, const MASK: u32, const THR: u32> Comparator<u32>
+    for MaskedPopCount32Comparator<SW, MASK, THR>
+{
     #[inline(always)]
-    fn is_match(value: u32) -> bool {
-        let r = value ^ Self::SYNCWORD;
+    fn syncword(&self) -> u32 {
+        SW & MASK
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        let r = (value ^ self.syncword()) & MASK;
 
         r.count_ones() <= THR
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        (value ^ self.syncword()) & MASK
+    }
+}
+
+impl Comparator<u16> for RuntimePopCount16Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        let r = value ^ self.syncword;
+
+        r.count_ones() <= self.threshold
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword
+    }
+}
+
+impl Comparator<u32> for RuntimePopCount32Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        let r = value ^ self.syncword;
+
+        r.count_ones() <= self.threshold
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword
+    }
 }
 
+impl Comparator<u64> for RuntimePopCount64Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        let r = value ^ self.syncword;
+
+        r.count_ones() <= self.threshold
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword
+    }
+}
+
+macro_rules! impl_hamming {
+    ($word:ty, $name:ident) => {
+        impl<const SW: $word, const THR: u32> HammingComparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn threshold(&self) -> u32 {
+                THR
+            }
+
+            #[inline(always)]
+            fn mask(&self) -> $word {
+                <$word>::MAX
+            }
+        }
+    };
+    ($word:ty, $name:ident, runtime) => {
+        impl HammingComparator<$word> for $name {
+            #[inline(always)]
+            fn threshold(&self) -> u32 {
+                self.threshold
+            }
+
+            #[inline(always)]
+            fn mask(&self) -> $word {
+                <$word>::MAX
+            }
+        }
+    };
+    ($word:ty, $name:ident, masked) => {
+        impl<const SW: $word, const MASK: $word, const THR: u32> HammingComparator<$word> for $name<SW, MASK, THR> {
+            #[inline(always)]
+            fn threshold(&self) -> u32 {
+                THR
+            }
+
+            #[inline(always)]
+            fn mask(&self) -> $word {
+                MASK
+            }
+        }
+    };
+}
+
+impl_hamming!(u8, PopCount8Comparator);
+impl_hamming!(u16, PopCount16Comparator);
+impl_hamming!(u32, PopCount32Comparator);
+impl_hamming!(u64, PopCount64Comparator);
+impl_hamming!(u16, RuntimePopCount16Comparator, runtime);
+impl_hamming!(u32, RuntimePopCount32Comparator, runtime);
+impl_hamming!(u64, RuntimePopCount64Comparator, runtime);
+impl_hamming!(u16, MaskedPopCount16Comparator, masked);
+impl_hamming!(u32, MaskedPopCount32Comparator, masked);
+
 #[cfg(test)]
 pub mod tests {
     use crate::comparators::Comparator;
@@ -33,25 +355,104 @@ pub mod tests {
 
     #[test]
     fn is_match_16() {
-        assert!(PopCount16Comparator::<0xFFFF, 1>::is_match(0xFFFF));
-        assert!(PopCount16Comparator::<0xFFFF, 1>::is_match(0xFFFE));
-        assert!(!PopCount16Comparator::<0xFFFF, 1>::is_match(0xFFFC));
+        assert!(PopCount16Comparator::<0xFFFF, 1>.is_match(0xFFFF));
+        assert!(PopCount16Comparator::<0xFFFF, 1>.is_match(0xFFFE));
+        assert!(!PopCount16Comparator::<0xFFFF, 1>.is_match(0xFFFC));
 
-        assert!(PopCount16Comparator::<0xFFFF, 2>::is_match(0xFFFF));
-        assert!(PopCount16Comparator::<0xFFFF, 2>::is_match(0xFFFE));
-        assert!(PopCount16Comparator::<0xFFFF, 2>::is_match(0xFFFC));
-        assert!(!PopCount16Comparator::<0xFFFF, 2>::is_match(0xFFF8));
+        assert!(PopCount16Comparator::<0xFFFF, 2>.is_match(0xFFFF));
+        assert!(PopCount16Comparator::<0xFFFF, 2>.is_match(0xFFFE));
+        assert!(PopCount16Comparator::<0xFFFF, 2>.is_match(0xFFFC));
+        assert!(!PopCount16Comparator::<0xFFFF, 2>.is_match(0xFFF8));
     }
 
     #[test]
     fn is_match_32() {
-        assert!(PopCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFF_FFFF));
-        assert!(PopCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFE_FFFF));
-        assert!(!PopCount32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFC_FFFF));
-
-        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFF_FFFF));
-        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFE_FFFF));
-        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFC_FFFF));
-        assert!(!PopCount32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFF8_FFFF));
+        assert!(PopCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFF_FFFF));
+        assert!(PopCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFE_FFFF));
+        assert!(!PopCount32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFC_FFFF));
+
+        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFF_FFFF));
+        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFE_FFFF));
+        assert!(PopCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFC_FFFF));
+        assert!(!PopCount32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFF8_FFFF));
+    }
+
+    #[test]
+    fn is_match_64() {
+        assert!(PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(!PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+
+        assert!(PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+        assert!(!PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFF8_FFFF_FFFF));
+        assert!(!PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0x7FFF_FFFF_FFFF_FFFC));
+    }
+
+    #[test]
+    fn is_match_bits() {
+        assert!(PopCountBitsComparator::<13, 0x1F35, 1>.is_match(0x1F35 << 115));
+        assert!(PopCountBitsComparator::<13, 0x1F35, 1>.is_match(0x1F35 << 115 | 0xFFFF));
+        assert!(PopCountBitsComparator::<13, 0x1F35, 1>.is_match(0x1F34 << 115));
+        assert!(!PopCountBitsComparator::<13, 0x1F35, 1>.is_match(0x1F36 << 115));
+
+        assert!(PopCountBitsComparator::<128, 0xFFFF, 2>.is_match(0xFFFC));
+        assert!(!PopCountBitsComparator::<128, 0xFFFF, 2>.is_match(0xFFF8));
+    }
+
+    #[test]
+    fn is_match_masked() {
+        assert!(MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 1>.is_match(0xF0FE));
+        assert!(!MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 1>.is_match(0xF0FC));
+
+        assert!(MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0, 2>.is_match(0xFFFC_FFF0));
+        assert!(!MaskedPopCount32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0, 2>.is_match(0xFFF8_FFF0));
+    }
+
+    #[test]
+    fn is_match_runtime() {
+        for threshold in 0..4 {
+            let comparator = RuntimePopCount16Comparator { syncword: 0xFFFF, threshold };
+            for errors in 0..8 {
+                let value = 0xFFFFu16 << errors;
+                assert_eq!(
+                    PopCount16Comparator::<0xFFFF, 3>.distance(value) <= threshold,
+                    comparator.is_match(value)
+                );
+            }
+        }
+
+        let comparator = RuntimePopCount32Comparator { syncword: 0xFFFF_FFFF, threshold: 2 };
+        assert!(comparator.is_match(0xFFFC_FFFF));
+        assert!(!comparator.is_match(0xFFF8_FFFF));
+
+        let comparator = RuntimePopCount64Comparator { syncword: 0xFFFF_FFFF_FFFF_FFFF, threshold: 2 };
+        assert!(comparator.is_match(0xFFFF_FFFC_FFFF_FFFF));
+        assert!(!comparator.is_match(0xFFFF_FFF8_FFFF_FFFF));
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(0, PopCount16Comparator::<0xFFFF, 2>.distance(0xFFFF));
+        assert_eq!(1, PopCount16Comparator::<0xFFFF, 2>.distance(0xFFFE));
+        assert_eq!(3, PopCount16Comparator::<0xFFFF, 2>.distance(0x7FFC));
+        assert_eq!(0x8003, PopCount16Comparator::<0xFFFF, 2>.error_mask(0x7FFC));
+
+        assert_eq!(0, PopCount32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFF_FFFF));
+        assert_eq!(1, PopCount32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFE_FFFF));
+        assert_eq!(3, PopCount32Comparator::<0xFFFFFFFF, 2>.distance(0x7FFC_FFFF));
+        assert_eq!(0x8003_0000, PopCount32Comparator::<0xFFFFFFFF, 2>.error_mask(0x7FFC_FFFF));
+
+        assert_eq!(0, PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFF_FFFF_FFFF));
+        assert_eq!(1, PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFE_FFFF_FFFF));
+        assert_eq!(3, PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0x7FFF_FFFF_FFFF_FFFC));
+        assert_eq!(0x8000_0000_0000_0003, PopCount64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.error_mask(0x7FFF_FFFF_FFFF_FFFC));
+
+        assert_eq!(1, PopCountBitsComparator::<13, 0x1F35, 2>.distance(0x1F34 << 115));
+        assert_eq!(0x0003, PopCountBitsComparator::<13, 0x1F35, 2>.error_mask(0x1F36 << 115));
+
+        assert_eq!(1, MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 2>.distance(0x70FF));
+        assert_eq!(0x8000, MaskedPopCount16Comparator::<0xFFFF, 0xF0FF, 2>.error_mask(0x70FF));
     }
 }
@@ -0,0 +1,227 @@
+use super::{Comparator, HammingComparator};
+
+// Comparators that count the differing bits in software, for targets where `count_ones()`
+// is not a single instruction, e.g. Cortex-M where it compiles to a library call or a loop.
+// They accept the same values as the `PopCount*` comparators with the same threshold.
+
+/// Comparator that counts the differing bits with a lookup in a 256 byte table for each byte.
+#[derive(Clone, Copy, Default)]
+pub struct LutPopCount16Comparator<const SW: u16, const THR: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct LutPopCount32Comparator<const SW: u32, const THR: u32>;
+
+/// Comparator that counts the differing bits with the branch free SWAR popcount from Hacker's Delight,
+/// i.e. by summing the bits of adjacent fields in parallel with shifts, ands and adds.
+#[derive(Clone, Copy, Default)]
+pub struct SwarPopCount16Comparator<const SW: u16, const THR: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct SwarPopCount32Comparator<const SW: u32, const THR: u32>;
+
+/// Comparator that clears one differing bit per iteration,
+/// and stops as soon as no differing bits are left or more than `THR` bits differ.
+#[derive(Clone, Copy, Default)]
+pub struct EarlyExitPopCount16Comparator<const SW: u16, const THR: u32>;
+#[derive(Clone, Copy, Default)]
+pub struct EarlyExitPopCount32Comparator<const SW: u32, const THR: u32>;
+
+/// The number of ones in each byte value.
+static BYTE_ONES: [u8; 256] = byte_ones();
+
+const fn byte_ones() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 1;
+    while i < 256 {
+        table[i] = table[i / 2] + (i & 1) as u8;
+        i += 1;
+    }
+    table
+}
+
+#[inline(always)]
+fn lut_ones16(x: u16) -> u32 {
+    BYTE_ONES[(x & 0xFF) as usize] as u32 + BYTE_ONES[(x >> 8) as usize] as u32
+}
+
+#[inline(always)]
+fn lut_ones32(x: u32) -> u32 {
+    BYTE_ONES[(x & 0xFF) as usize] as u32
+        + BYTE_ONES[((x >> 8) & 0xFF) as usize] as u32
+        + BYTE_ONES[((x >> 16) & 0xFF) as usize] as u32
+        + BYTE_ONES[(x >> 24) as usize] as u32
+}
+
+#[inline(always)]
+fn swar_ones16(x: u16) -> u32 {
+    let x = x - ((x >> 1) & 0x5555);
+    let x = (x & 0x3333) + ((x >> 2) & 0x3333);
+    let x = (x + (x >> 4)) & 0x0F0F;
+    ((x + (x >> 8)) & 0x1F) as u32
+}
+
+#[inline(always)]
+fn swar_ones32(x: u32) -> u32 {
+    let x = x - ((x >> 1) & 0x5555_5555);
+    let x = (x & 0x3333_3333) + ((x >> 2) & 0x3333_3333);
+    let x = (x + (x >> 4)) & 0x0F0F_0F0F;
+    let x = x + (x >> 8);
+    (x + (x >> 16)) & 0x3F
+}
+
+macro_rules! impl_comparator {
+    ($name:ident, $word:ty, $ones:ident) => {
+        impl<const SW: $word, const THR: u32> Comparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn syncword(&self) -> $word {
+                SW
+            }
+
+            #[inline(always)]
+            fn is_match(&self, value: $word) -> bool {
+                let r = value ^ self.syncword();
+
+                $ones(r) <= THR
+            }
+
+            #[inline(always)]
+            fn distance(&self, value: $word) -> u32 {
+                $ones(self.error_mask(value))
+            }
+
+            #[inline(always)]
+            fn error_mask(&self, value: $word) -> $word {
+                value ^ self.syncword()
+            }
+        }
+
+        impl<const SW: $word, const THR: u32> HammingComparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn threshold(&self) -> u32 {
+                THR
+            }
+
+            #[inline(always)]
+            fn mask(&self) -> $word {
+                <$word>::MAX
+            }
+        }
+    };
+}
+
+impl_comparator!(LutPopCount16Comparator, u16, lut_ones16);
+impl_comparator!(LutPopCount32Comparator, u32, lut_ones32);
+impl_comparator!(SwarPopCount16Comparator, u16, swar_ones16);
+impl_comparator!(SwarPopCount32Comparator, u32, swar_ones32);
+
+macro_rules! impl_early_exit {
+    ($name:ident, $word:ty) => {
+        impl<const SW: $word, const THR: u32> Comparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn syncword(&self) -> $word {
+                SW
+            }
+
+            #[inline(always)]
+            fn is_match(&self, value: $word) -> bool {
+                let mut r = value ^ self.syncword();
+
+                for _ in 0..THR {
+                    if r == 0 {
+                        return true;
+                    }
+                    // Clear the lowest differing bit.
+                    r &= r - 1;
+                }
+
+                r == 0
+            }
+
+            #[inline(always)]
+            fn distance(&self, value: $word) -> u32 {
+                let mut r = self.error_mask(value);
+                let mut ones = 0;
+
+                while r != 0 {
+                    r &= r - 1;
+                    ones += 1;
+                }
+
+                ones
+            }
+
+            #[inline(always)]
+            fn error_mask(&self, value: $word) -> $word {
+                value ^ self.syncword()
+            }
+        }
+
+        impl<const SW: $word, const THR: u32> HammingComparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn threshold(&self) -> u32 {
+                THR
+            }
+
+            #[inline(always)]
+            fn mask(&self) -> $word {
+                <$word>::MAX
+            }
+        }
+    };
+}
+
+impl_early_exit!(EarlyExitPopCount16Comparator, u16);
+impl_early_exit!(EarlyExitPopCount32Comparator, u32);
+
+#[cfg(test)]
+pub mod tests {
+    use crate::comparators::{PopCount16Comparator, PopCount32Comparator};
+
+    use super::*;
+
+    macro_rules! assert_equivalent16 {
+        ($sw:literal, $($thr:literal),*) => {
+            $(
+                let expected = PopCount16Comparator::<$sw, $thr>;
+                for value in 0..=u16::MAX {
+                    let is_match = expected.is_match(value);
+                    let distance = expected.distance(value);
+                    assert_eq!(is_match, LutPopCount16Comparator::<$sw, $thr>.is_match(value));
+                    assert_eq!(distance, LutPopCount16Comparator::<$sw, $thr>.distance(value));
+                    assert_eq!(is_match, SwarPopCount16Comparator::<$sw, $thr>.is_match(value));
+                    assert_eq!(distance, SwarPopCount16Comparator::<$sw, $thr>.distance(value));
+                    assert_eq!(is_match, EarlyExitPopCount16Comparator::<$sw, $thr>.is_match(value));
+                    assert_eq!(distance, EarlyExitPopCount16Comparator::<$sw, $thr>.distance(value));
+                }
+            )*
+        };
+    }
+
+    #[test]
+    fn equivalent_16() {
+        assert_equivalent16!(0xFFFF, 0, 1, 2, 3, 4, 16);
+        assert_equivalent16!(0x9AC5, 0, 1, 2, 3, 4, 16);
+    }
+
+    #[test]
+    fn equivalent_32() {
+        let expected = PopCount32Comparator::<0x9AC5_3F1D, 3>;
+        let check = |value: u32| {
+            let is_match = expected.is_match(value);
+            let distance = expected.distance(value);
+            assert_eq!(is_match, LutPopCount32Comparator::<0x9AC5_3F1D, 3>.is_match(value));
+            assert_eq!(distance, LutPopCount32Comparator::<0x9AC5_3F1D, 3>.distance(value));
+            assert_eq!(is_match, SwarPopCount32Comparator::<0x9AC5_3F1D, 3>.is_match(value));
+            assert_eq!(distance, SwarPopCount32Comparator::<0x9AC5_3F1D, 3>.distance(value));
+            assert_eq!(is_match, EarlyExitPopCount32Comparator::<0x9AC5_3F1D, 3>.is_match(value));
+            assert_eq!(distance, EarlyExitPopCount32Comparator::<0x9AC5_3F1D, 3>.distance(value));
+        };
+
+        // All values of either half, with the other half equal to the syncword or zero.
+        for half in 0..=u16::MAX as u32 {
+            check(0x9AC5_0000 | half);
+            check(half << 16 | 0x3F1D);
+            check(half);
+            check(half << 16);
+        }
+        check(u32::MAX);
+    }
+}
@@ -1,36 +1,298 @@
-use super::Comparator;
+use super::{syncword_bits, Comparator};
 
+#[derive(Clone, Copy, Default)]
 pub struct TwosComplement16Comparator<const SW: u16, const THR: usize>;
+#[derive(Clone, Copy, Default)]
 pub struct TwosComplement32Comparator<const SW: u32, const THR: usize>;
+#[derive(Clone, Copy, Default)]
+pub struct TwosComplement64Comparator<const SW: u64, const THR: usize>;
+
+/// Comparator for a `BITS` bit syncword, 1 to 128 bits, that is compared with the top `BITS` bits of a 128 bit value.
+#[derive(Clone, Copy, Default)]
+pub struct TwosComplementBitsComparator<const BITS: usize, const SW: u128, const THR: usize>;
+
+/// Comparator for a syncword and threshold that are configured at runtime.
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeTwosComplement16Comparator {
+    pub syncword: u16,
+    pub threshold: usize,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeTwosComplement32Comparator {
+    pub syncword: u32,
+    pub threshold: usize,
+}
+#[derive(Clone, Copy, Debug)]
+pub struct RuntimeTwosComplement64Comparator {
+    pub syncword: u64,
+    pub threshold: usize,
+}
+
+/// Comparator for a syncword where only the bits set in `MASK` are compared,
+/// and `THR` is the number of compared bits that may differ.
+#[derive(Clone, Copy, Default)]
+pub struct MaskedTwosComplement16Comparator<const SW: u16, const MASK: u16, const THR: usize>;
+#[derive(Clone, Copy, Default)]
+pub struct MaskedTwosComplement32Comparator<const SW: u32, const MASK: u32, const THR: usize>;
 
 impl<const SW: u16, const THR: usize> Comparator<u16> for TwosComplement16Comparator<SW, THR> {
-    const SYNCWORD: u16 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW
+    }
 
     #[inline(always)]
-    fn is_match(value: u16) -> bool {
-        let mut r = (value ^ Self::SYNCWORD) as i16;
+    fn is_match(&self, value: u16) -> bool {
+        let mut r = (value ^ self.syncword()) as i16;
 
         for _ in 0..THR {
-            r = r ^ (r & -r);
+            r ^= r & r.wrapping_neg();
         }
 
         r == 0
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword()
+    }
 }
 
 impl<const SW: u32, const THR: usize> Comparator<u32> for TwosComplement32Comparator<SW, THR> {
-    const SYNCWORD: u32 = SW;
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        let mut r = (value ^ self.syncword()) as i32;
+
+        for _ in 0..THR {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const SW: u64, const THR: usize> Comparator<u64> for TwosComplement64Comparator<SW, THR> {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        let mut r = (value ^ self.syncword()) as i64;
+
+        for _ in 0..THR {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword()
+    }
+}
+
+impl<const BITS: usize, const SW: u128, const THR: usize> Comparator<u128>
+    for TwosComplementBitsComparator<BITS, SW, THR>
+{
+    const SYNCWORD_BITS: usize = syncword_bits(BITS, SW);
+
+    #[inline(always)]
+    fn syncword(&self) -> u128 {
+        SW
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u128) -> bool {
+        let mut r = ((value >> (128 - Self::SYNCWORD_BITS)) ^ self.syncword()) as i128;
+
+        for _ in 0..THR {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u128) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u128) -> u128 {
+        (value >> (128 - Self::SYNCWORD_BITS)) ^ self.syncword()
+    }
+}
+
+impl<const SW: u16, const MASK: u16, const THR: usize> Comparator<u16>
+    for MaskedTwosComplement16Comparator<SW, MASK, THR>
+{
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        SW & MASK
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        let mut r = ((value ^ self.syncword()) & MASK) as i16;
+
+        for _ in 0..THR {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        (value ^ self.syncword()) & MASK
+    }
+}
+
+impl<const SW: u32, const MASK: u32, const THR: usize> Comparator<u32>
+    for MaskedTwosComplement32Comparator<SW, MASK, THR>
+{
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        SW & MASK
+    }
 
     #[inline(always)]
-    fn is_match(value: u32) -> bool {
-        let mut r = (value ^ Self::SYNCWORD) as i32;
+    fn is_match(&self, value: u32) -> bool {
+        let mut r = ((value ^ self.syncword()) & MASK) as i32;
 
         for _ in 0..THR {
-            r = r ^ (r & -r);
+            r ^= r & r.wrapping_neg();
         }
 
         r == 0
     }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        (value ^ self.syncword()) & MASK
+    }
+}
+
+impl Comparator<u16> for RuntimeTwosComplement16Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u16 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u16) -> bool {
+        let mut r = (value ^ self.syncword) as i16;
+
+        for _ in 0..self.threshold {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u16) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u16) -> u16 {
+        value ^ self.syncword
+    }
+}
+
+impl Comparator<u32> for RuntimeTwosComplement32Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u32 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u32) -> bool {
+        let mut r = (value ^ self.syncword) as i32;
+
+        for _ in 0..self.threshold {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u32) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u32) -> u32 {
+        value ^ self.syncword
+    }
+}
+
+impl Comparator<u64> for RuntimeTwosComplement64Comparator {
+    #[inline(always)]
+    fn syncword(&self) -> u64 {
+        self.syncword
+    }
+
+    #[inline(always)]
+    fn is_match(&self, value: u64) -> bool {
+        let mut r = (value ^ self.syncword) as i64;
+
+        for _ in 0..self.threshold {
+            r ^= r & r.wrapping_neg();
+        }
+
+        r == 0
+    }
+
+    #[inline(always)]
+    fn distance(&self, value: u64) -> u32 {
+        self.error_mask(value).count_ones()
+    }
+
+    #[inline(always)]
+    fn error_mask(&self, value: u64) -> u64 {
+        value ^ self.syncword
+    }
 }
 
 #[cfg(test)]
@@ -41,25 +303,94 @@ pub mod tests {
 
     #[test]
     fn is_match_16() {
-        assert!(TwosComplement16Comparator::<0xFFFF, 1>::is_match(0xFFFF));
-        assert!(TwosComplement16Comparator::<0xFFFF, 1>::is_match(0xFFFE));
-        assert!(!TwosComplement16Comparator::<0xFFFF, 1>::is_match(0xFFFC));
+        assert!(TwosComplement16Comparator::<0xFFFF, 1>.is_match(0xFFFF));
+        assert!(TwosComplement16Comparator::<0xFFFF, 1>.is_match(0xFFFE));
+        assert!(!TwosComplement16Comparator::<0xFFFF, 1>.is_match(0xFFFC));
 
-        assert!(TwosComplement16Comparator::<0xFFFF, 2>::is_match(0xFFFF));
-        assert!(TwosComplement16Comparator::<0xFFFF, 2>::is_match(0xFFFE));
-        assert!(TwosComplement16Comparator::<0xFFFF, 2>::is_match(0xFFFC));
-        assert!(!TwosComplement16Comparator::<0xFFFF, 2>::is_match(0xFFF8));
+        assert!(TwosComplement16Comparator::<0xFFFF, 2>.is_match(0xFFFF));
+        assert!(TwosComplement16Comparator::<0xFFFF, 2>.is_match(0xFFFE));
+        assert!(TwosComplement16Comparator::<0xFFFF, 2>.is_match(0xFFFC));
+        assert!(!TwosComplement16Comparator::<0xFFFF, 2>.is_match(0xFFF8));
     }
 
     #[test]
     fn is_match_32() {
-        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFF_FFFF));
-        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFE_FFFF));
-        assert!(!TwosComplement32Comparator::<0xFFFFFFFF, 1>::is_match(0xFFFC_FFFF));
-
-        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFF_FFFF));
-        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFE_FFFF));
-        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFFC_FFFF));
-        assert!(!TwosComplement32Comparator::<0xFFFFFFFF, 2>::is_match(0xFFF8_FFFF));
+        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFF_FFFF));
+        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFE_FFFF));
+        assert!(!TwosComplement32Comparator::<0xFFFFFFFF, 1>.is_match(0xFFFC_FFFF));
+
+        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFF_FFFF));
+        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFE_FFFF));
+        assert!(TwosComplement32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFFC_FFFF));
+        assert!(!TwosComplement32Comparator::<0xFFFFFFFF, 2>.is_match(0xFFF8_FFFF));
+    }
+
+    #[test]
+    fn is_match_64() {
+        assert!(TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(!TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 1>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+
+        assert!(TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFF_FFFF_FFFF));
+        assert!(TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFE_FFFF_FFFF));
+        assert!(TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFFC_FFFF_FFFF));
+        assert!(!TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0xFFFF_FFF8_FFFF_FFFF));
+        assert!(!TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.is_match(0x7FFF_FFFF_FFFF_FFFC));
+    }
+
+    #[test]
+    fn is_match_bits() {
+        assert!(TwosComplementBitsComparator::<13, 0x1F35, 1>.is_match(0x1F35 << 115));
+        assert!(TwosComplementBitsComparator::<13, 0x1F35, 1>.is_match(0x1F35 << 115 | 0xFFFF));
+        assert!(TwosComplementBitsComparator::<13, 0x1F35, 1>.is_match(0x1F34 << 115));
+        assert!(!TwosComplementBitsComparator::<13, 0x1F35, 1>.is_match(0x1F36 << 115));
+
+        assert!(TwosComplementBitsComparator::<128, 0xFFFF, 2>.is_match(0xFFFC));
+        assert!(!TwosComplementBitsComparator::<128, 0xFFFF, 2>.is_match(0xFFF8));
+    }
+
+    #[test]
+    fn is_match_masked() {
+        assert!(MaskedTwosComplement16Comparator::<0xFFFF, 0xF0FF, 1>.is_match(0xF0FE));
+        assert!(!MaskedTwosComplement16Comparator::<0xFFFF, 0xF0FF, 1>.is_match(0xF0FC));
+
+        assert!(MaskedTwosComplement32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0, 2>.is_match(0xFFFC_FFF0));
+        assert!(!MaskedTwosComplement32Comparator::<0xFFFFFFFF, 0xFFFF_FFF0, 2>.is_match(0xFFF8_FFF0));
+    }
+
+    #[test]
+    fn is_match_runtime() {
+        let comparator = RuntimeTwosComplement16Comparator { syncword: 0xFFFF, threshold: 2 };
+        assert!(comparator.is_match(0xFFFC));
+        assert!(!comparator.is_match(0xFFF8));
+
+        let comparator = RuntimeTwosComplement32Comparator { syncword: 0xFFFF_FFFF, threshold: 2 };
+        assert!(comparator.is_match(0xFFFC_FFFF));
+        assert!(!comparator.is_match(0xFFF8_FFFF));
+
+        let comparator = RuntimeTwosComplement64Comparator { syncword: 0xFFFF_FFFF_FFFF_FFFF, threshold: 2 };
+        assert!(comparator.is_match(0xFFFF_FFFC_FFFF_FFFF));
+        assert!(!comparator.is_match(0xFFFF_FFF8_FFFF_FFFF));
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(0, TwosComplement16Comparator::<0xFFFF, 2>.distance(0xFFFF));
+        assert_eq!(1, TwosComplement16Comparator::<0xFFFF, 2>.distance(0xFFFE));
+        assert_eq!(3, TwosComplement16Comparator::<0xFFFF, 2>.distance(0x7FFC));
+        assert_eq!(0x8003, TwosComplement16Comparator::<0xFFFF, 2>.error_mask(0x7FFC));
+
+        assert_eq!(0, TwosComplement32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFF_FFFF));
+        assert_eq!(1, TwosComplement32Comparator::<0xFFFFFFFF, 2>.distance(0xFFFE_FFFF));
+        assert_eq!(3, TwosComplement32Comparator::<0xFFFFFFFF, 2>.distance(0x7FFC_FFFF));
+        assert_eq!(0x8003_0000, TwosComplement32Comparator::<0xFFFFFFFF, 2>.error_mask(0x7FFC_FFFF));
+
+        assert_eq!(0, TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFF_FFFF_FFFF));
+        assert_eq!(1, TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0xFFFF_FFFE_FFFF_FFFF));
+        assert_eq!(3, TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.distance(0x7FFF_FFFF_FFFF_FFFC));
+        assert_eq!(0x8000_0000_0000_0003, TwosComplement64Comparator::<0xFFFFFFFFFFFFFFFF, 2>.error_mask(0x7FFF_FFFF_FFFF_FFFC));
+
+        assert_eq!(1, TwosComplementBitsComparator::<13, 0x1F35, 2>.distance(0x1F34 << 115));
+        assert_eq!(0x0003, TwosComplementBitsComparator::<13, 0x1F35, 2>.error_mask(0x1F36 << 115));
     }
 }
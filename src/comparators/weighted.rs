@@ -0,0 +1,134 @@
+use super::Comparator;
+
+/// Comparator where each differing bit adds its weight in `weights`, and `THR` is the largest accepted sum of weights,
+/// e.g. to give less weight to the first bits after the preamble while the AFC settles.
+/// `weights[0]` is the weight of the first transmitted bit, i.e. the most significant syncword bit.
+/// `distance()` is still the number of differing bits, and `weight()` is the sum of their weights.
+///
+/// It works with the detectors that call `is_match()` for each offset, i.e. the single, double and quad detectors.
+/// It is not a `HammingComparator`, as a threshold of differing bits cannot express the weights,
+/// so the detectors that count differing bits themselves, e.g. `BitSliced16Detector`, do not accept it.
+///
+/// ```
+/// use drone_framesync::{comparators::Weighted16Comparator, detectors::{Detector, Single16Detector}};
+///
+/// let weights = [1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2];
+/// let detector = Single16Detector::with_comparator(Weighted16Comparator::<0x9AC5, 2> { weights });
+/// // The first two bits differ, with a weight of one each.
+/// assert_eq!(Some(8), detector.position(&[0x00, 0x5A, 0xC5, 0x00]));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Weighted16Comparator<const SW: u16, const THR: u32> {
+    pub weights: [u8; 16],
+}
+#[derive(Clone, Copy, Debug)]
+pub struct Weighted32Comparator<const SW: u32, const THR: u32> {
+    pub weights: [u8; 32],
+}
+
+macro_rules! impl_weighted {
+    ($name:ident, $word:ty, $bits:literal) => {
+        impl<const SW: $word, const THR: u32> $name<SW, THR> {
+            /// Get the sum of the weights of the bits in `value` that differ from the syncword.
+            pub fn weight(&self, value: $word) -> u32 {
+                self.weight_up_to(value, u32::MAX)
+            }
+
+            /// Add the weight of one differing bit at a time, from the first bit,
+            /// and stop as soon as the sum exceeds `limit`.
+            #[inline(always)]
+            fn weight_up_to(&self, value: $word, limit: u32) -> u32 {
+                let mut r = value ^ SW;
+                let mut sum = 0;
+
+                while r != 0 && sum <= limit {
+                    let index = r.leading_zeros() as usize;
+                    sum += self.weights[index] as u32;
+                    r &= !(1 << ($bits - 1 - index));
+                }
+
+                sum
+            }
+        }
+
+        impl<const SW: $word, const THR: u32> Comparator<$word> for $name<SW, THR> {
+            #[inline(always)]
+            fn syncword(&self) -> $word {
+                SW
+            }
+
+            #[inline(always)]
+            fn is_match(&self, value: $word) -> bool {
+                self.weight_up_to(value, THR) <= THR
+            }
+
+            #[inline(always)]
+            fn distance(&self, value: $word) -> u32 {
+                self.error_mask(value).count_ones()
+            }
+
+            #[inline(always)]
+            fn error_mask(&self, value: $word) -> $word {
+                value ^ self.syncword()
+            }
+        }
+    };
+}
+
+impl_weighted!(Weighted16Comparator, u16, 16);
+impl_weighted!(Weighted32Comparator, u32, 32);
+
+#[cfg(test)]
+pub mod tests {
+    use crate::comparators::{PopCount16Comparator, PopCount32Comparator};
+
+    use super::*;
+
+    const AFC: Weighted16Comparator<0xFFFF, 2> = Weighted16Comparator {
+        weights: [1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+    };
+
+    #[test]
+    fn is_match_16() {
+        // The first four bits have half the weight of the others.
+        assert!(AFC.is_match(0xFFFF));
+        assert!(AFC.is_match(0x3FFF));
+        assert!(AFC.is_match(0xFFFE));
+        assert!(!AFC.is_match(0x7FFE));
+        assert!(!AFC.is_match(0xFFFC));
+
+        let afc = Weighted16Comparator::<0xFFFF, 4> { weights: AFC.weights };
+        assert!(afc.is_match(0x0FFF));
+        assert!(!afc.is_match(0x0FFE));
+    }
+
+    #[test]
+    fn unit_weights() {
+        // Weights of one accept the same values as a popcount comparator.
+        for value in 0..=u16::MAX {
+            assert_eq!(
+                PopCount16Comparator::<0x9AC5, 3>.is_match(value),
+                Weighted16Comparator::<0x9AC5, 3> { weights: [1; 16] }.is_match(value)
+            );
+        }
+
+        for shift in 0..32 {
+            let value = 0x9AC5_3F1D ^ (0x0000_0007 << shift);
+            assert_eq!(
+                PopCount32Comparator::<0x9AC5_3F1D, 2>.is_match(value),
+                Weighted32Comparator::<0x9AC5_3F1D, 2> { weights: [1; 32] }.is_match(value)
+            );
+        }
+    }
+
+    #[test]
+    fn weight() {
+        assert_eq!(0, AFC.weight(0xFFFF));
+        assert_eq!(3, AFC.weight(0x7FFE));
+        assert_eq!(2, AFC.distance(0x7FFE));
+        assert_eq!(0x8001, AFC.error_mask(0x7FFE));
+        assert_eq!(28, AFC.weight(0x0000));
+
+        assert_eq!(7, Weighted32Comparator::<0x0000_0000, 2> { weights: [1; 32] }.weight(0x8000_003F));
+    }
+}
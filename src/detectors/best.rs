@@ -0,0 +1,88 @@
+//! The fastest detectors for the target architecture, i.e. the table of `x86_64`, `cortexm4` or `cortexm0`,
+//! and the `portable` table for other targets.
+//!
+//! ```
+//! use drone_framesync::detectors::{best, Detector};
+//!
+//! static DETECTOR: best::Sync32<0xFFFFFFFF, 2> = best::sync32::<0xFFFFFFFF, 2>();
+//! let position = best::sync16_tol1::<0xFFFF>().position(&[0u8; 32]);
+//! ```
+
+macro_rules! use_table {
+    ($table:ident) => {
+        pub use super::$table::{
+            sync16, sync16_tol0, sync16_tol1, sync16_tol2, sync16_tol3, sync16_tol4, sync32, sync32_tol0, sync32_tol1,
+            sync32_tol2, sync32_tol3, sync32_tol4, sync32_tol5, sync32_tol6, Sync16, Sync32, Table,
+        };
+    };
+}
+
+#[cfg(target_arch = "x86_64")]
+use_table!(x86_64);
+
+// Cortex-M3 and M4 implement ARMv7-M with CLZ, while Cortex-M0 and M0+ implement ARMv6-M.
+#[cfg(all(target_arch = "arm", target_feature = "v7"))]
+use_table!(cortexm4);
+
+#[cfg(all(target_arch = "arm", not(target_feature = "v7")))]
+use_table!(cortexm0);
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "arm")))]
+use_table!(portable);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        detectors::{cortexm0, cortexm4, portable, Detector},
+        lcg::Lcg,
+    };
+
+    /// Create haystacks with the syncword inserted at random positions with a few random bit errors.
+    fn haystacks(syncword: u32, bits: usize) -> Vec<Vec<u8>> {
+        let mut rng = Lcg::default();
+        let mut next = move || rng.next_u64();
+
+        (0..200)
+            .map(|_| {
+                let length = 8 + (next() % 24) as usize;
+                let mut haystack: Vec<u8> = (0..length).map(|_| next() as u8).collect();
+                let position = (next() % (8 * length as u64 - bits as u64)) as usize;
+                let errors = next() & next() & next();
+
+                for i in 0..bits {
+                    let bit = (syncword >> (bits - 1 - i)) & 1 == 1;
+                    let bit = bit ^ ((errors >> i) & 1 == 1);
+                    let (byte, shift) = ((position + i) / 8, 7 - (position + i) % 8);
+                    haystack[byte] = haystack[byte] & !(1 << shift) | (bit as u8) << shift;
+                }
+                haystack
+            })
+            .collect()
+    }
+
+    fn positions<D: Detector<T>, T>(detector: D, haystacks: &[Vec<u8>]) -> Vec<Option<usize>> {
+        haystacks.iter().map(|haystack| detector.position(haystack)).collect()
+    }
+
+    macro_rules! assert_identical {
+        ($sync:ident, $sw:literal, $bits:literal, $($tol:literal),*) => {
+            let haystacks = haystacks($sw, $bits);
+            $(
+                let expected = positions(super::$sync::<$sw, $tol>(), &haystacks);
+                assert_eq!(expected, positions(cortexm4::$sync::<$sw, $tol>(), &haystacks));
+                assert_eq!(expected, positions(cortexm0::$sync::<$sw, $tol>(), &haystacks));
+                assert_eq!(expected, positions(portable::$sync::<$sw, $tol>(), &haystacks));
+                #[cfg(target_arch = "x86_64")]
+                assert_eq!(expected, positions(crate::detectors::x86_64::$sync::<$sw, $tol>(), &haystacks));
+            )*
+        };
+    }
+
+    #[test]
+    fn identical_positions() {
+        assert_identical!(sync16, 0x9AC5, 16, 0, 1, 2, 3, 4);
+        assert_identical!(sync32, 0x9AC5_3F1D, 32, 0, 1, 2, 3, 4, 5, 6);
+    }
+}
@@ -0,0 +1,138 @@
+use super::{Detector, Match};
+
+/// A detector that, once the wrapped detector first accepts an offset, keeps evaluating the following `N` offsets
+/// and reports the one with the lowest distance to the syncword. Ties are broken by the lowest position.
+/// All matches within the `N` offsets are considered to be the same syncword and are not reported individually.
+pub struct BestMatchDetector<D, const N: usize> {
+    detector: D,
+}
+
+/// The state of an ongoing search with a `BestMatchDetector`.
+#[derive(Clone, Copy)]
+pub struct BestMatchCursor<C, T> {
+    cursor: C,
+    /// The first match after the neighbourhood of the previously reported match.
+    lookahead: Option<Match<T>>,
+}
+
+impl<C: Default, T> Default for BestMatchCursor<C, T> {
+    fn default() -> Self {
+        Self {
+            cursor: C::default(),
+            lookahead: None,
+        }
+    }
+}
+
+impl<D, const N: usize> BestMatchDetector<D, N> {
+    pub const fn new(detector: D) -> Self {
+        Self { detector }
+    }
+}
+
+impl<D: Detector<T>, T: Copy, const N: usize> Detector<T> for BestMatchDetector<D, N> {
+    type Block = D::Block;
+    type Cursor = BestMatchCursor<D::Cursor, T>;
+    const SYNCWORD_BITS: usize = D::SYNCWORD_BITS;
+
+    fn syncword(&self) -> T {
+        self.detector.syncword()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self::Block {
+        D::from_slice(slice)
+    }
+
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<T>> {
+        let first = match cursor.lookahead.take() {
+            Some(lookahead) => lookahead,
+            None => self.detector.resume(&mut cursor.cursor, haystack)?,
+        };
+
+        // Evaluate all matches in the neighbourhood following the first match.
+        let mut best = first;
+        loop {
+            match self.detector.resume(&mut cursor.cursor, haystack) {
+                Some(m) if m.position <= first.position + N => {
+                    if m.errors < best.errors {
+                        best = m;
+                    }
+                }
+                next => {
+                    cursor.lookahead = next;
+                    break;
+                }
+            }
+        }
+
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{PopCount16Comparator, PopCount32Comparator},
+        detectors::{Double16Detector, Single32Detector},
+    };
+
+    use super::*;
+
+    #[test]
+    fn position_of_lowest_distance() {
+        let first = Single32Detector::<PopCount32Comparator<0xFFFFFFFF, 2>>::new();
+        let best = BestMatchDetector::<_, 2>::new(first);
+
+        // The syncword starts at position 16, but two bit errors are also accepted at position 14.
+        let haystack = [0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00];
+
+        assert_eq!(Some(14), best.detector.position(&haystack));
+        assert_eq!(
+            Some(Match {
+                position: 16,
+                errors: 0,
+                error_mask: 0,
+                syncword_index: 0,
+                inverted: false
+            }),
+            best.find(&haystack)
+        );
+    }
+
+    #[test]
+    fn ties_are_broken_by_lowest_position() {
+        let best = BestMatchDetector::<_, 4>::new(Double16Detector::<
+            PopCount16Comparator<0xFFFF, 1>,
+        >::new());
+
+        // A run of 17 ones starting at position 1, i.e. position 1 and 2 are both exact.
+        let haystack = [0x7F, 0xFF, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+        assert_eq!(Some(0), best.detector.position(&haystack));
+        assert_eq!(Some(1), best.position(&haystack));
+    }
+
+    #[test]
+    fn neighbourhood_is_reported_once() {
+        let detector = Double16Detector::<PopCount16Comparator<0xFFFF, 1>>::new();
+        let best = BestMatchDetector::<_, 4>::new(detector);
+
+        // Two runs of 17 ones, i.e. two syncwords that each match in several neighbouring positions.
+        let haystack = [
+            0xFF, 0xFF, 0x80, 0x00, 0x00, 0x7F, 0xFF, 0xC0, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let blocks = haystack
+            .chunks_exact(4)
+            .map(Double16Detector::<PopCount16Comparator<0xFFFF, 1>>::from_slice);
+
+        let found: Vec<usize> = best.positions_in_blocks(blocks).collect();
+
+        assert_eq!(vec![0, 41], found);
+    }
+}
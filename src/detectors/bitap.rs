@@ -0,0 +1,246 @@
+use core::marker::PhantomData;
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{ConstDefault, HammingComparator},
+    word::Word,
+};
+
+use super::{Detector, Match};
+
+/// Detector that runs the bitap (shift-and) algorithm one haystack bit at a time,
+/// with one state register for each number of bit errors up to `threshold()`.
+/// The cost per bit only grows with one shift, and, or per extra allowed error,
+/// and the number of errors of a match follows from the lowest register that accepts it.
+#[derive(Default)]
+pub struct Bitap16Detector<C: HammingComparator<u16>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// Detector for a 32 bit syncword, see `Bitap16Detector`.
+#[derive(Default)]
+pub struct Bitap32Detector<C: HammingComparator<u32>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// The largest number of state registers, i.e. for a 32 bit syncword where all bits may be errors.
+const MAX_REGISTERS: usize = 33;
+
+/// The state of an ongoing search with a bitap detector.
+#[derive(Clone, Copy)]
+pub struct BitapCursor {
+    current: Option<u32>,
+    next: u32,
+    /// The next block is loaded into `next`.
+    loaded: bool,
+    offset: usize,
+    /// The number of haystack bits searched.
+    searched: usize,
+    /// The last searched haystack bits.
+    window: u32,
+    /// Bit `j` of register `d` is set when the first `j + 1` syncword bits end at the last searched bit with at most `d` errors.
+    registers: [u32; MAX_REGISTERS],
+}
+
+impl Default for BitapCursor {
+    fn default() -> Self {
+        Self {
+            current: None,
+            next: 0,
+            loaded: false,
+            offset: 0,
+            searched: 0,
+            window: 0,
+            registers: [0; MAX_REGISTERS],
+        }
+    }
+}
+
+macro_rules! impl_bitap {
+    ($name:ident, $word:ty) => {
+        impl<C: HammingComparator<$word>> $name<C> {
+            /// Create a detector using the comparator with a constant syncword.
+            pub const fn new() -> Self
+            where
+                C: ConstDefault,
+            {
+                Self::with_comparator(C::DEFAULT)
+            }
+
+            /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+            pub const fn with_comparator(comparator: C) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> $name<C, O> {
+            /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+            pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> Detector<$word> for $name<C, O> {
+            type Block = u32;
+            type Cursor = BitapCursor;
+
+            fn syncword(&self) -> $word {
+                self.comparator.syncword()
+            }
+
+            fn from_slice(slice: &[u8]) -> Self::Block {
+                u32::from_slice(slice)
+            }
+
+            fn resume<I: Iterator<Item = Self::Block>>(
+                &self,
+                cursor: &mut Self::Cursor,
+                haystack: &mut I,
+            ) -> Option<Match<$word>> {
+                const BITS: usize = <$word as Word>::BITS;
+                const TOP: u32 = 1 << (BITS - 1);
+
+                // The syncword bits that are ones and zeros, where bit `j` is the `j`th transmitted syncword bit.
+                // The bits that are not compared are in both, so that they match either haystack bit.
+                let ignored = !(self.comparator.mask() as u32).reverse_bits() >> (32 - BITS);
+                let ones = (self.comparator.syncword() as u32).reverse_bits() >> (32 - BITS) | ignored;
+                let zeros = !ones & (u32::MAX >> (32 - BITS)) | ignored;
+                let errors = (self.comparator.threshold() as usize).min(BITS);
+
+                let mut current = match cursor.current {
+                    Some(current) => current,
+                    None => {
+                        // Load the first 32 bit block.
+                        let block = haystack.next()?;
+                        O::load32(block)
+                    }
+                };
+                let mut next = cursor.next;
+                let mut loaded = cursor.loaded;
+                let mut offset = cursor.offset;
+                let mut searched = cursor.searched;
+                let mut window = cursor.window;
+                let mut registers = cursor.registers;
+                let registers = &mut registers[..errors + 1];
+
+                // Iterate for each bit of the haystack one at a time.
+                let position = 'search: loop {
+                    if offset == 31 && !loaded {
+                        // The last bit of the block is searched when the next block is available,
+                        // so that there is no match in the last possible position.
+                        match haystack.next() {
+                            Some(block) => next = O::load32(block),
+                            None => break 'search None,
+                        }
+                        loaded = true;
+                    }
+
+                    if offset == 32 {
+                        // Set "next" as "current" for the next iteration.
+                        current = next;
+                        loaded = false;
+                        offset = 0;
+                    }
+
+                    let bit = (current << offset) >> 31;
+                    let mask = if bit == 1 { ones } else { zeros };
+                    offset += 1;
+                    searched += 1;
+                    window = window << 1 | bit;
+
+                    // A syncword bit either matches the haystack bit, or it is an error.
+                    let mut previous = registers[0];
+                    registers[0] = (previous << 1 | 1) & mask;
+                    for register in registers[1..].iter_mut() {
+                        let old = *register;
+                        *register = (old << 1 | 1) & mask | (previous << 1 | 1);
+                        previous = old;
+                    }
+
+                    if registers[errors] & TOP != 0 {
+                        let value = window as $word;
+                        let errors = registers.iter().position(|register| register & TOP != 0).unwrap() as u32;
+                        break 'search Some(Match {
+                            errors,
+                            ..Match::new(&self.comparator, searched - BITS, value)
+                        });
+                    }
+                };
+
+                let mut saved = [0; MAX_REGISTERS];
+                saved[..errors + 1].copy_from_slice(registers);
+                *cursor = BitapCursor {
+                    current: Some(current),
+                    next,
+                    loaded,
+                    offset,
+                    searched,
+                    window,
+                    registers: saved,
+                };
+
+                position
+            }
+        }
+    };
+}
+
+impl_bitap!(Bitap16Detector, u16);
+impl_bitap!(Bitap32Detector, u32);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{PopCount16Comparator, PopCount32Comparator},
+        lcg::Lcg,
+    };
+
+    use super::*;
+
+    #[test]
+    fn errors_in_registers() {
+        let mut rng = Lcg::default();
+        let haystack: Vec<u32> = (0..64).map(|_| (rng.next_u64() >> 32) as u32).collect();
+        let bits: Vec<bool> = haystack
+            .iter()
+            .flat_map(|block| (0..32).map(move |i| (block.to_be() << i) & 0x8000_0000 != 0))
+            .collect();
+        let blocks = || haystack.iter().copied();
+
+        let detector = Bitap16Detector::<PopCount16Comparator<0x9AC5, 5>>::new();
+        let expected: Vec<(usize, u32)> = (0..bits.len() - 16)
+            .map(|position| {
+                let value = bits[position..position + 16]
+                    .iter()
+                    .fold(0u16, |value, &bit| value << 1 | bit as u16);
+                (position, (value ^ 0x9AC5).count_ones())
+            })
+            .filter(|&(_, errors)| errors <= 5)
+            .collect();
+        let found: Vec<(usize, u32)> = detector.matches_in_blocks(blocks()).map(|m| (m.position, m.errors)).collect();
+        assert_eq!(expected, found);
+
+        let detector = Bitap32Detector::<PopCount32Comparator<0x9AC5_3F1D, 12>>::new();
+        let expected: Vec<(usize, u32)> = (0..bits.len() - 32)
+            .map(|position| {
+                let value = bits[position..position + 32]
+                    .iter()
+                    .fold(0u32, |value, &bit| value << 1 | bit as u32);
+                (position, (value ^ 0x9AC5_3F1D).count_ones())
+            })
+            .filter(|&(_, errors)| errors <= 12)
+            .collect();
+        let found: Vec<(usize, u32)> = detector.matches_in_blocks(blocks()).map(|m| (m.position, m.errors)).collect();
+        assert_eq!(expected, found);
+    }
+}
@@ -0,0 +1,287 @@
+use core::{convert::TryInto, marker::PhantomData, mem};
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{Comparator, ConstDefault},
+};
+
+use super::{Detector, Match};
+
+/// Detector for syncwords of any length up to 128 bits.
+/// The comparator is given a 128 bit window where the syncword is aligned to the most significant bit,
+/// so that it only compares the top `C::SYNCWORD_BITS` bits.
+#[derive(Default)]
+pub struct BitsDetector<C: Comparator<u128>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// The state of an ongoing search with a `BitsDetector`.
+#[derive(Clone, Copy, Default)]
+pub struct BitsCursor {
+    window: u128,
+    next: u32,
+    /// The position of the first bit in the window.
+    position: usize,
+    offset: usize,
+    /// The total number of bits in the haystack, when it is exhausted.
+    end: Option<usize>,
+    loaded: bool,
+}
+
+impl<C: Comparator<u128>> BitsDetector<C> {
+    /// Create a detector using the comparator with a constant syncword.
+    pub const fn new() -> Self
+    where
+        C: ConstDefault,
+    {
+        Self::with_comparator(C::DEFAULT)
+    }
+
+    /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+    pub const fn with_comparator(comparator: C) -> Self {
+        Self {
+            comparator,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<C: Comparator<u128>, O: BitOrder> BitsDetector<C, O> {
+    /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+    pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+        Self {
+            comparator,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<C: Comparator<u128>, O: BitOrder> Detector<u128> for BitsDetector<C, O> {
+    type Block = u32;
+    type Cursor = BitsCursor;
+    const SYNCWORD_BITS: usize = C::SYNCWORD_BITS;
+
+    fn syncword(&self) -> u128 {
+        self.comparator.syncword()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self::Block {
+        let bytes: [u8; mem::size_of::<u32>()] = slice.try_into().unwrap();
+        unsafe { mem::transmute(bytes) }
+    }
+
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<u128>> {
+        let mut window = cursor.window;
+        let mut next = cursor.next;
+        let mut position = cursor.position;
+        let mut offset = cursor.offset;
+        let mut end = cursor.end;
+
+        if !cursor.loaded {
+            // Load the first four 32 bit blocks into the 128 bit window.
+            let mut loaded = 0;
+            while loaded < 128 {
+                match haystack.next() {
+                    Some(block) => window = (window << 32) | O::load32(block) as u128,
+                    None => break,
+                }
+                loaded += 32;
+            }
+
+            if loaded == 0 {
+                return None;
+            } else if loaded < 128 {
+                window <<= 128 - loaded;
+                end = Some(loaded);
+            }
+
+            offset = 32;
+        }
+
+        let found = 'search: loop {
+            if let Some(end) = end {
+                // Search the remaining positions where the syncword is within the haystack.
+                while position + C::SYNCWORD_BITS < end {
+                    let value = window;
+
+                    window <<= 1;
+                    position += 1;
+
+                    if self.comparator.is_match(value) {
+                        break 'search Some(Match::new(&self.comparator, position - 1, value));
+                    }
+                }
+
+                break 'search None;
+            }
+
+            if offset == 32 {
+                match haystack.next() {
+                    Some(block) => {
+                        next = O::load32(block);
+                        offset = 0;
+                    }
+                    None => {
+                        end = Some(position + 128);
+                        continue;
+                    }
+                }
+            }
+
+            // Search the window while shifting in the next 32 bit block, one bit at a time.
+            while offset < 32 {
+                let value = window;
+
+                window = (window << 1) | (next >> 31) as u128;
+                next <<= 1;
+                offset += 1;
+                position += 1;
+
+                if self.comparator.is_match(value) {
+                    break 'search Some(Match::new(&self.comparator, position - 1, value));
+                }
+            }
+        };
+
+        *cursor = BitsCursor {
+            window,
+            next,
+            position,
+            offset,
+            end,
+            loaded: true,
+        };
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{ExactBitsComparator, PopCountBitsComparator},
+        LsbFirst,
+    };
+
+    use super::*;
+    use bitvec::prelude::*;
+
+    fn insert(haystack: &mut [u8], position: usize, syncword: u128, bits: usize) {
+        let view = haystack.view_bits_mut::<Msb0>();
+        for i in 0..bits {
+            view.set(position + i, (syncword >> (bits - 1 - i)) & 1 == 1);
+        }
+    }
+
+    fn assert_position<C: Comparator<u128> + ConstDefault>() {
+        let detector = BitsDetector::<C>::new();
+        let syncword = detector.syncword();
+        let bits = C::SYNCWORD_BITS;
+
+        for length in 0..40usize {
+            for position in 0..(8 * length).saturating_sub(bits) {
+                let mut haystack = vec![0u8; length];
+                insert(&mut haystack, position, syncword, bits);
+
+                assert_eq!(
+                    Some(position),
+                    detector.position(&haystack),
+                    "Not found in {:?}",
+                    haystack
+                );
+            }
+
+            if 8 * length >= bits {
+                let mut haystack = vec![0u8; length];
+                insert(&mut haystack, 8 * length - bits, syncword, bits);
+
+                assert_eq!(None, detector.position(&haystack));
+            }
+        }
+    }
+
+    #[test]
+    fn position_barker13() {
+        assert_position::<ExactBitsComparator<13, 0x1F35>>();
+    }
+
+    #[test]
+    fn position_21() {
+        assert_position::<ExactBitsComparator<21, 0x1A2B3D>>();
+    }
+
+    #[test]
+    fn position_24() {
+        assert_position::<ExactBitsComparator<24, 0x8E89BF>>();
+    }
+
+    #[test]
+    fn position_48() {
+        assert_position::<ExactBitsComparator<48, 0xD5A5_1234_5679>>();
+    }
+
+    #[test]
+    fn position_128() {
+        assert_position::<ExactBitsComparator<128, 0x8123_4567_89AB_CDEF_0123_4567_89AB_CDEF>>();
+    }
+
+    #[test]
+    fn positions_in_blocks() {
+        let detector = BitsDetector::<ExactBitsComparator<13, 0x1F35>>::new();
+
+        let mut haystack = vec![0u8; 40];
+        insert(&mut haystack, 3, 0x1F35, 13);
+        insert(&mut haystack, 100, 0x1F35, 13);
+        insert(&mut haystack, 250, 0x1F35, 13);
+
+        let blocks = haystack
+            .chunks_exact(4)
+            .map(BitsDetector::<ExactBitsComparator<13, 0x1F35>>::from_slice);
+        let found: Vec<usize> = detector.positions_in_blocks(blocks).collect();
+
+        assert_eq!(vec![3, 100, 250], found);
+    }
+
+    #[test]
+    fn lsb_first() {
+        let detector = BitsDetector::with_bit_order(ExactBitsComparator::<21, 0x1A2B3D>, LsbFirst);
+
+        for position in 0..8 * 12 - 21 {
+            let mut haystack = vec![0u8; 12];
+            {
+                let view = haystack.view_bits_mut::<Lsb0>();
+                for i in 0..21 {
+                    view.set(position + i, (0x1A2B3D >> (20 - i)) & 1 == 1);
+                }
+            }
+
+            assert_eq!(Some(position), detector.position(&haystack));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let detector = BitsDetector::<PopCountBitsComparator<13, 0x1F35, 1>>::new();
+
+        let mut haystack = vec![0u8; 8];
+        insert(&mut haystack, 20, 0x1F35 ^ 0x0040, 13);
+
+        assert_eq!(
+            Some(Match {
+                position: 20,
+                errors: 1,
+                error_mask: 0x0040,
+                syncword_index: 0,
+                inverted: false
+            }),
+            detector.find(&haystack)
+        );
+    }
+}
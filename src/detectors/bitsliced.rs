@@ -0,0 +1,286 @@
+use core::marker::PhantomData;
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{ConstDefault, HammingComparator},
+    word::Word,
+};
+
+use super::{Detector, Match};
+
+/// Detector that computes the Hamming distance to the syncword at all 32 offsets of a 32 bit block in parallel,
+/// and accepts the offsets with at most `threshold()` bit errors.
+/// The distances are kept in bit-sliced vertical counters, i.e. one `u32` for each counter bit with one bit per offset,
+/// so that the cost per block does not depend on the number of offsets and only grows slowly with the threshold.
+#[derive(Default)]
+pub struct BitSliced16Detector<C: HammingComparator<u16>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// Detector for a 32 bit syncword, see `BitSliced16Detector`.
+#[derive(Default)]
+pub struct BitSliced32Detector<C: HammingComparator<u32>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// The state of an ongoing search with a bit-sliced detector.
+#[derive(Clone, Copy, Default)]
+pub struct BitSlicedCursor {
+    current: Option<u32>,
+    next: u32,
+    index: usize,
+    /// The accepted offsets of the current block that are not yet reported, one bit per offset.
+    pending: u32,
+    /// The accepted offsets of the current block are found.
+    loaded: bool,
+    /// The haystack is exhausted and the current block is the last.
+    last: bool,
+}
+
+/// Get the offsets 0..32 in `window`, where offset 0 is the most significant bit,
+/// at which the `bits` bit syncword in the most significant bits of `syncword` is found with at most `threshold` bit errors.
+/// Only the syncword bits set in `mask`, aligned like `syncword`, are compared.
+/// The accepted offsets are returned with offset 0 in the most significant bit.
+#[inline(always)]
+pub(super) fn accepted(window: u64, syncword: u32, mask: u32, bits: usize, threshold: u32) -> u32 {
+    // A runtime threshold may be larger than the number of syncword bits, which accepts all offsets.
+    let threshold = threshold.min(bits as u32);
+    // The number of counter bits needed to count to the threshold, larger counts are kept in `overflow`.
+    let counter_bits = (32 - threshold.leading_zeros()) as usize;
+    let mut counters = [0u32; 6];
+    let mut overflow = 0;
+
+    for i in 0..bits {
+        if mask & (0x8000_0000 >> i) == 0 {
+            continue;
+        }
+
+        // The haystack bits compared with syncword bit `i`, for all offsets.
+        let haystack = ((window << i) >> 32) as u32;
+        let expected = if syncword & (0x8000_0000 >> i) != 0 { u32::MAX } else { 0 };
+
+        // Add the errors to the counters, one bit-sliced half adder for each counter bit.
+        let mut carry = haystack ^ expected;
+        for counter in counters[..counter_bits].iter_mut() {
+            let sum = *counter ^ carry;
+            carry &= *counter;
+            *counter = sum;
+        }
+        overflow |= carry;
+
+        if overflow == u32::MAX {
+            // No offset can be accepted.
+            return 0;
+        }
+    }
+
+    // Compare the counters with the threshold, starting with the most significant counter bit.
+    let mut greater = overflow;
+    let mut equal = u32::MAX;
+    for bit in (0..counter_bits).rev() {
+        if threshold & (1 << bit) == 0 {
+            greater |= equal & counters[bit];
+            equal &= !counters[bit];
+        } else {
+            equal &= counters[bit];
+        }
+    }
+
+    !greater
+}
+
+macro_rules! impl_bitsliced {
+    ($name:ident, $word:ty) => {
+        impl<C: HammingComparator<$word>> $name<C> {
+            /// Create a detector using the comparator with a constant syncword.
+            pub const fn new() -> Self
+            where
+                C: ConstDefault,
+            {
+                Self::with_comparator(C::DEFAULT)
+            }
+
+            /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+            pub const fn with_comparator(comparator: C) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> $name<C, O> {
+            /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+            pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> Detector<$word> for $name<C, O> {
+            type Block = u32;
+            type Cursor = BitSlicedCursor;
+
+            fn syncword(&self) -> $word {
+                self.comparator.syncword()
+            }
+
+            fn from_slice(slice: &[u8]) -> Self::Block {
+                u32::from_slice(slice)
+            }
+
+            fn resume<I: Iterator<Item = Self::Block>>(
+                &self,
+                cursor: &mut Self::Cursor,
+                haystack: &mut I,
+            ) -> Option<Match<$word>> {
+                const BITS: usize = <$word as Word>::BITS;
+                let syncword = (self.comparator.syncword() as u32) << (32 - BITS);
+                let mask = (self.comparator.mask() as u32) << (32 - BITS);
+                let threshold = self.comparator.threshold();
+
+                let mut current = match cursor.current {
+                    Some(current) => current,
+                    None => {
+                        // Load the first 32 bit block.
+                        let block = haystack.next()?;
+                        O::load32(block)
+                    }
+                };
+                let mut next = cursor.next;
+                let mut index = cursor.index;
+                let mut pending = cursor.pending;
+                let mut loaded = cursor.loaded;
+                let mut last = cursor.last;
+
+                // Iterate for each of the next 32 bit blocks one at a time.
+                let position = 'search: loop {
+                    if !loaded {
+                        match haystack.next() {
+                            Some(block) => {
+                                next = O::load32(block);
+                                let window = (current as u64) << 32 | next as u64;
+                                pending = accepted(window, syncword, mask, BITS, threshold);
+                            }
+                            None if !last => {
+                                // Only the offsets where the syncword is fully within the last block,
+                                // except for the last possible position, are searched.
+                                next = 0;
+                                let window = (current as u64) << 32;
+                                pending = accepted(window, syncword, mask, BITS, threshold) & !(u32::MAX >> (32 - BITS));
+                                last = true;
+                            }
+                            None => break 'search None,
+                        }
+                        loaded = true;
+                    }
+
+                    if pending != 0 {
+                        let offset = pending.leading_zeros() as usize;
+                        pending &= !(0x8000_0000 >> offset);
+
+                        let window = (current as u64) << 32 | next as u64;
+                        let value = (window << offset >> (64 - BITS)) as $word;
+                        break 'search Some(Match::new(&self.comparator, 32 * index + offset, value));
+                    }
+
+                    if last {
+                        break 'search None;
+                    }
+
+                    // Set "next" as "current" for the next iteration.
+                    current = next;
+                    loaded = false;
+                    index += 1;
+                };
+
+                *cursor = BitSlicedCursor {
+                    current: Some(current),
+                    next,
+                    index,
+                    pending,
+                    loaded,
+                    last,
+                };
+
+                position
+            }
+        }
+    };
+}
+
+impl_bitsliced!(BitSliced16Detector, u16);
+impl_bitsliced!(BitSliced32Detector, u32);
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{PopCount16Comparator, RuntimePopCount16Comparator, RuntimePopCount32Comparator},
+        lcg::Lcg,
+    };
+
+    use super::*;
+
+    #[test]
+    fn accepted_offsets() {
+        let mut rng = Lcg::default();
+        for _ in 0..1000 {
+            let state = rng.next_u64();
+            let syncword = (state >> 32) as u32;
+            let window = state ^ (syncword as u64) << 16;
+
+            // All bits compared, and about a quarter of the bits masked out.
+            for &mask in [u32::MAX, !(state as u32 & (state >> 7) as u32)].iter() {
+                for &bits in [16, 32].iter() {
+                    for threshold in 0..8 {
+                        let syncword = syncword & (u32::MAX << (32 - bits));
+                        let expected = (0..32)
+                            .filter(|offset| {
+                                let value = ((window << offset) >> 32) as u32 & (u32::MAX << (32 - bits));
+                                ((value ^ syncword) & mask).count_ones() <= threshold
+                            })
+                            .fold(0, |accepted, offset| accepted | 0x8000_0000 >> offset);
+
+                        assert_eq!(expected, accepted(window, syncword, mask, bits, threshold));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn threshold_above_word_width() {
+        let detector = BitSliced16Detector::with_comparator(RuntimePopCount16Comparator { syncword: 0x9AC5, threshold: 64 });
+        assert_eq!(Some(0), detector.position(&[0x00, 0x00, 0x00, 0x00]));
+
+        let detector = BitSliced32Detector::with_comparator(RuntimePopCount32Comparator { syncword: 0x9AC5_3F1D, threshold: 33 });
+        assert_eq!(Some(0), detector.position(&[0x00, 0x00, 0x00, 0x00, 0x00]));
+    }
+
+    #[test]
+    fn positions_overlapping() {
+        let detector = BitSliced16Detector::<PopCount16Comparator<0xFFFF, 0>>::new();
+
+        for length in 1..6 {
+            for position in 0..(32 * length as usize).saturating_sub(16 + 2) {
+                let mut haystack = vec![0u32; length];
+                for i in position..position + 16 + 2 {
+                    haystack[i / 32] |= 0x8000_0000 >> (i % 32);
+                }
+                let haystack: Vec<u32> = haystack.iter().map(|block| block.to_be()).collect();
+
+                let found: Vec<usize> = detector
+                    .positions_in_blocks(haystack.iter().copied())
+                    .collect();
+
+                assert_eq!(vec![position, position + 1, position + 2], found);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! The detectors for Cortex-M0 and M0+, selected from their instruction counts, as they have not been benchmarked.
+
+use crate::comparators::{
+    Exact16Comparator, Exact32Comparator, PopCount16Comparator, PopCount32Comparator, TwosComplement16Comparator,
+    TwosComplement32Comparator,
+};
+
+use super::{
+    select::{impl_sync, impl_table},
+    BitSliced16Detector, BitSliced32Detector, Single16Detector, Single32Detector,
+};
+
+// Cortex-M0 and M0+ have no CLZ instruction and `count_ones()` is a software routine,
+// so neither `LeadingZeroCount*` nor `PopCount*` comparators are used with the single detectors.
+// `TwosComplement*` clears one error bit per iteration with a subtraction and an and,
+// and is used for the lowest tolerances where it only needs a few iterations per offset.
+// The bit-sliced detectors only use shifts, xor and and, with a cost per block that grows with the number
+// of counter bits, i.e. logarithmically with the tolerance, so they are used for the higher tolerances.
+// The selection is estimated from these instruction counts and is not tuned, as it has not been measured
+// on a Cortex-M0 or M0+ core. Replace it with the fastest lines of a benchmark on such a core once available.
+
+/// The marker type of the Cortex-M0 table, for the `select::Select*` traits.
+pub struct Table;
+
+impl_table!(
+    "on Cortex-M0, estimated from the instruction counts,";
+    sync16, Sync16, Select16<u16>, 16, "0 to 4";
+    sync32, Sync32, Select32<u32>, 32, "0 to 6"
+);
+
+impl_sync!(sync16_tol0<u16>, Select16<0>, Single16Detector, Exact16Comparator::<SW>);
+impl_sync!(sync16_tol1<u16>, Select16<1>, Single16Detector, TwosComplement16Comparator::<SW, 1>);
+impl_sync!(sync16_tol2<u16>, Select16<2>, Single16Detector, TwosComplement16Comparator::<SW, 2>);
+impl_sync!(sync16_tol3<u16>, Select16<3>, BitSliced16Detector, PopCount16Comparator::<SW, 3>);
+impl_sync!(sync16_tol4<u16>, Select16<4>, BitSliced16Detector, PopCount16Comparator::<SW, 4>);
+
+impl_sync!(sync32_tol0<u32>, Select32<0>, Single32Detector, Exact32Comparator::<SW>);
+impl_sync!(sync32_tol1<u32>, Select32<1>, Single32Detector, TwosComplement32Comparator::<SW, 1>);
+impl_sync!(sync32_tol2<u32>, Select32<2>, Single32Detector, TwosComplement32Comparator::<SW, 2>);
+impl_sync!(sync32_tol3<u32>, Select32<3>, BitSliced32Detector, PopCount32Comparator::<SW, 3>);
+impl_sync!(sync32_tol4<u32>, Select32<4>, BitSliced32Detector, PopCount32Comparator::<SW, 4>);
+impl_sync!(sync32_tol5<u32>, Select32<5>, BitSliced32Detector, PopCount32Comparator::<SW, 5>);
+impl_sync!(sync32_tol6<u32>, Select32<6>, BitSliced32Detector, PopCount32Comparator::<SW, 6>);
+
+#[cfg(test)]
+mod tests {
+    use crate::detectors::Detector;
+
+    use super::*;
+
+    const DETECTOR: Sync32<0xFFFFFFFF, 6> = sync32::<0xFFFFFFFF, 6>();
+
+    #[test]
+    fn nameable_detectors() {
+        // The tolerance of six bits accepts the syncword six bits before it starts.
+        assert_eq!(Some(2), DETECTOR.position(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]));
+
+        let haystack = [0x00, 0x9A, 0xC5, 0x3F, 0x1D, 0x00];
+        assert_eq!(Some(8), sync16::<0x9AC5, 0>().position(&haystack));
+        assert_eq!(Some(8), sync16::<0x9AC5, 3>().position(&haystack));
+        assert_eq!(Some(8), sync32_tol1::<0x9AC5_3F1D>().position(&haystack));
+    }
+}
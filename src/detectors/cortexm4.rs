@@ -1,9 +1,31 @@
+//! The fastest detectors on Cortex-M4, selected from the benchmark output below.
+//!
+//! ```
+//! use drone_framesync::detectors::cortexm4::{sync16, Sync16};
+//!
+//! static DETECTOR: Sync16<0xFFFF, 2> = sync16::<0xFFFF, 2>();
+//! ```
+//!
+//! An unsupported tolerance fails to compile:
+//!
+//! ```compile_fail
+//! use drone_framesync::detectors::cortexm4::{sync16, Sync16};
+//!
+//! static DETECTOR: Sync16<0xFFFF, 5> = sync16::<0xFFFF, 5>();
+//! ```
+
 use crate::comparators::{
-    Exact16Comparator, Exact32Comparator, PopCount32Comparator, TwosComplement16Comparator,
-    TwosComplement32Comparator,
+    Exact16Comparator, Exact32Comparator, Exact64Comparator, MaskedExact16Comparator,
+    MaskedExact32Comparator, MaskedPopCount32Comparator, MaskedTwosComplement16Comparator,
+    MaskedTwosComplement32Comparator, PopCount32Comparator, PopCount64Comparator,
+    TwosComplement16Comparator, TwosComplement32Comparator, TwosComplement64Comparator, Weighted16Comparator,
+    Weighted32Comparator,
 };
 
-use super::{Detector, Single16Detector, Single32Detector};
+use super::{
+    select::{impl_sync, impl_table},
+    Single16Detector, Single32Detector, Single64Detector,
+};
 
 // Output from benchmark
 // single16;exact;2288
@@ -70,65 +92,260 @@ use super::{Detector, Single16Detector, Single32Detector};
 // double32;twoscmpl4;26466
 // double32;twoscmpl5;32168
 // double32;twoscmpl6;35106
+//
+// The benchmark also lists bitsliced16 and bitsliced32, which have not been run on Cortex-M4 yet.
+// The selections below only change from measured output, so they stay with the single detectors
+// until the bit-sliced lines are added here and beat them.
 
-macro_rules! impl_sync {
-    ($name:ident<$type:ty>, $detector:ident, $comparator:ty) => {
-        pub const fn $name<const SW: $type>() -> impl Detector<$type> {
-            $detector::<$comparator>::new()
-        }
-    };
-}
+/// The marker type of the Cortex-M4 table, for the `select::Select*` traits.
+pub struct Table;
+
+impl_table!(
+    "on Cortex-M4";
+    sync16, Sync16, Select16<u16>, 16, "0 to 4";
+    sync32, Sync32, Select32<u32>, 32, "0 to 6";
+    sync64, Sync64, Select64<u64>, 64, "0 to 6"
+);
 
-impl_sync!(sync16_tol0<u16>, Single16Detector, Exact16Comparator::<SW>);
+impl_sync!(
+    sync16_tol0<u16>,
+    Select16<0>,
+    Single16Detector,
+    Exact16Comparator::<SW>
+);
 impl_sync!(
     sync16_tol1<u16>,
+    Select16<1>,
     Single16Detector,
     TwosComplement16Comparator::<SW, 1>
 );
 impl_sync!(
     sync16_tol2<u16>,
+    Select16<2>,
     Single16Detector,
     TwosComplement16Comparator::<SW, 2>
 );
 impl_sync!(
     sync16_tol3<u16>,
+    Select16<3>,
     Single16Detector,
     TwosComplement16Comparator::<SW, 3>
 );
 impl_sync!(
     sync16_tol4<u16>,
+    Select16<4>,
     Single16Detector,
     TwosComplement16Comparator::<SW, 4>
 );
 
-impl_sync!(sync32_tol0<u32>, Single32Detector, Exact32Comparator::<SW>);
+impl_sync!(
+    sync32_tol0<u32>,
+    Select32<0>,
+    Single32Detector,
+    Exact32Comparator::<SW>
+);
 impl_sync!(
     sync32_tol1<u32>,
+    Select32<1>,
     Single32Detector,
     TwosComplement32Comparator::<SW, 1>
 );
 impl_sync!(
     sync32_tol2<u32>,
+    Select32<2>,
     Single32Detector,
     TwosComplement32Comparator::<SW, 2>
 );
 impl_sync!(
     sync32_tol3<u32>,
+    Select32<3>,
     Single32Detector,
     TwosComplement32Comparator::<SW, 3>
 );
 impl_sync!(
     sync32_tol4<u32>,
+    Select32<4>,
     Single32Detector,
     TwosComplement32Comparator::<SW, 4>
 );
 impl_sync!(
     sync32_tol5<u32>,
+    Select32<5>,
     Single32Detector,
     TwosComplement32Comparator::<SW, 5>
 );
 impl_sync!(
     sync32_tol6<u32>,
+    Select32<6>,
     Single32Detector,
     PopCount32Comparator::<SW, 6>
 );
+
+// No Cortex-M4 output of the single64 and double64 benchmark entries is recorded yet,
+// so the 64 bit selections are provisional and follow the 32 bit results, where the single detectors are the fastest.
+// Replace them with the fastest single64 or double64 line once measured, and check with best64 that they match.
+impl_sync!(
+    sync64_tol0<u64>,
+    Select64<0>,
+    Single64Detector,
+    Exact64Comparator::<SW>
+);
+impl_sync!(
+    sync64_tol1<u64>,
+    Select64<1>,
+    Single64Detector,
+    TwosComplement64Comparator::<SW, 1>
+);
+impl_sync!(
+    sync64_tol2<u64>,
+    Select64<2>,
+    Single64Detector,
+    TwosComplement64Comparator::<SW, 2>
+);
+impl_sync!(
+    sync64_tol3<u64>,
+    Select64<3>,
+    Single64Detector,
+    TwosComplement64Comparator::<SW, 3>
+);
+impl_sync!(
+    sync64_tol4<u64>,
+    Select64<4>,
+    Single64Detector,
+    TwosComplement64Comparator::<SW, 4>
+);
+impl_sync!(
+    sync64_tol5<u64>,
+    Select64<5>,
+    Single64Detector,
+    TwosComplement64Comparator::<SW, 5>
+);
+impl_sync!(
+    sync64_tol6<u64>,
+    Select64<6>,
+    Single64Detector,
+    PopCount64Comparator::<SW, 6>
+);
+
+// The masked selections follow the unmasked results, as masking only adds a single and instruction.
+impl_sync!(
+    sync16_masked_tol0<u16, MASK>,
+    Single16Detector,
+    MaskedExact16Comparator::<SW, MASK>
+);
+impl_sync!(
+    sync16_masked_tol1<u16, MASK>,
+    Single16Detector,
+    MaskedTwosComplement16Comparator::<SW, MASK, 1>
+);
+impl_sync!(
+    sync16_masked_tol2<u16, MASK>,
+    Single16Detector,
+    MaskedTwosComplement16Comparator::<SW, MASK, 2>
+);
+impl_sync!(
+    sync16_masked_tol3<u16, MASK>,
+    Single16Detector,
+    MaskedTwosComplement16Comparator::<SW, MASK, 3>
+);
+impl_sync!(
+    sync16_masked_tol4<u16, MASK>,
+    Single16Detector,
+    MaskedTwosComplement16Comparator::<SW, MASK, 4>
+);
+
+impl_sync!(
+    sync32_masked_tol0<u32, MASK>,
+    Single32Detector,
+    MaskedExact32Comparator::<SW, MASK>
+);
+impl_sync!(
+    sync32_masked_tol1<u32, MASK>,
+    Single32Detector,
+    MaskedTwosComplement32Comparator::<SW, MASK, 1>
+);
+impl_sync!(
+    sync32_masked_tol2<u32, MASK>,
+    Single32Detector,
+    MaskedTwosComplement32Comparator::<SW, MASK, 2>
+);
+impl_sync!(
+    sync32_masked_tol3<u32, MASK>,
+    Single32Detector,
+    MaskedTwosComplement32Comparator::<SW, MASK, 3>
+);
+impl_sync!(
+    sync32_masked_tol4<u32, MASK>,
+    Single32Detector,
+    MaskedTwosComplement32Comparator::<SW, MASK, 4>
+);
+impl_sync!(
+    sync32_masked_tol5<u32, MASK>,
+    Single32Detector,
+    MaskedTwosComplement32Comparator::<SW, MASK, 5>
+);
+impl_sync!(
+    sync32_masked_tol6<u32, MASK>,
+    Single32Detector,
+    MaskedPopCount32Comparator::<SW, MASK, 6>
+);
+
+// The weighted comparator visits each differing bit with CLZ like `LeadingZeroCount*`,
+// so its selections follow the unweighted results where the single detectors are the fastest.
+
+/// The fastest detector for the 16 bit syncword `SW` where each differing bit adds its weight,
+/// and the sum of weights may be at most `THR`, see `Weighted16Comparator`.
+pub type Weighted16<const SW: u16, const THR: u32> = Single16Detector<Weighted16Comparator<SW, THR>>;
+
+/// The fastest detector for the 32 bit syncword `SW` with per bit weights, see `Weighted16`.
+pub type Weighted32<const SW: u32, const THR: u32> = Single32Detector<Weighted32Comparator<SW, THR>>;
+
+/// Create the fastest detector for the 16 bit syncword `SW` with the per bit `weights`.
+pub const fn sync16_weighted<const SW: u16, const THR: u32>(weights: [u8; 16]) -> Weighted16<SW, THR> {
+    Single16Detector::with_comparator(Weighted16Comparator { weights })
+}
+
+/// Create the fastest detector for the 32 bit syncword `SW` with the per bit `weights`.
+pub const fn sync32_weighted<const SW: u32, const THR: u32>(weights: [u8; 32]) -> Weighted32<SW, THR> {
+    Single32Detector::with_comparator(Weighted32Comparator { weights })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{detectors::Detector, SyncWindow};
+
+    use super::*;
+
+    const DETECTOR: Sync32<0xFFFFFFFF, 6> = sync32::<0xFFFFFFFF, 6>();
+
+    struct Driver {
+        window: SyncWindow<Sync16<0xFFFF, 1>, u16>,
+    }
+
+    #[test]
+    fn nameable_detectors() {
+        let mut driver = Driver {
+            window: SyncWindow::new(sync16::<0xFFFF, 1>()),
+        };
+        driver.window.extend(&[0x00, 0xFF, 0xFE, 0x00]);
+        assert_eq!(1, driver.window.detect().count());
+
+        // The tolerance of six bits accepts the syncword six bits before it starts.
+        assert_eq!(Some(2), DETECTOR.position(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]));
+
+        let haystack = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+        assert_eq!(Some(8), sync64::<0xFFFFFFFFFFFFFFFF, 0>().position(&haystack));
+    }
+
+    const AFC: [u8; 16] = [1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2];
+    static WEIGHTED: Weighted16<0xFFFF, 2> = sync16_weighted::<0xFFFF, 2>(AFC);
+
+    #[test]
+    fn weighted_detectors() {
+        // Two errors in the first bits are accepted, but not two errors in the last bits.
+        assert_eq!(Some(8), WEIGHTED.position(&[0x00, 0x3F, 0xFF, 0x00]));
+        assert_eq!(None, WEIGHTED.position(&[0x00, 0xFF, 0x7E, 0x00]));
+
+        assert_eq!(Some(8), sync32_weighted::<0x9AC5_3F1D, 1>([1; 32]).position(&[0x00, 0x9A, 0xC5, 0x3F, 0x1C, 0x00]));
+    }
+}
@@ -0,0 +1,218 @@
+use core::marker::PhantomData;
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{Comparator, ConstDefault},
+    word::Word,
+};
+
+use super::{Detector, Match};
+
+/// Detector that searches for a `W` syncword in blocks of two words,
+/// by shifting a window of the block and the next word one bit at a time, i.e. two offsets are compared for each shift.
+#[derive(Default)]
+pub struct DoubleDetector<W: Word, C: Comparator<W>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    word: PhantomData<W>,
+    bit_order: PhantomData<O>,
+}
+
+pub type Double8Detector<C, O = MsbFirst> = DoubleDetector<u8, C, O>;
+pub type Double16Detector<C, O = MsbFirst> = DoubleDetector<u16, C, O>;
+pub type Double32Detector<C, O = MsbFirst> = DoubleDetector<u32, C, O>;
+pub type Double64Detector<C, O = MsbFirst> = DoubleDetector<u64, C, O>;
+
+/// The state of an ongoing search with a `DoubleDetector`.
+#[derive(Clone, Copy, Default)]
+pub struct DoubleCursor<W: Word> {
+    current: Option<W::Double>,
+    window: W::Window,
+    next: W::Double,
+    index: usize,
+    offset: usize,
+    /// Matches in the second word of the block that are not yet reported, one bit per offset.
+    pending: W,
+    /// The haystack is exhausted and only the first word of the last block remains.
+    last: bool,
+}
+
+impl<W: Word, C: Comparator<W>> DoubleDetector<W, C> {
+    /// Create a detector using the comparator with a constant syncword.
+    pub const fn new() -> Self
+    where
+        C: ConstDefault,
+    {
+        Self::with_comparator(C::DEFAULT)
+    }
+
+    /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+    pub const fn with_comparator(comparator: C) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: Word, C: Comparator<W>, O: BitOrder> DoubleDetector<W, C, O> {
+    /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+    pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: Word, C: Comparator<W>, O: BitOrder> Detector<W> for DoubleDetector<W, C, O> {
+    type Block = W::Double;
+    type Cursor = DoubleCursor<W>;
+
+    fn syncword(&self) -> W {
+        self.comparator.syncword()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self::Block {
+        W::double_from_slice(slice)
+    }
+
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<W>> {
+        let mut current = match cursor.current {
+            Some(current) => current,
+            None => {
+                // Load the first block.
+                let block = haystack.next()?;
+                W::load_double::<O>(block)
+            }
+        };
+        let mut window = cursor.window;
+        let mut next = cursor.next;
+        let mut index = cursor.index;
+        let mut offset = cursor.offset;
+        let mut pending = cursor.pending;
+        let mut last = cursor.last;
+
+        // Iterate for each of the next blocks one at a time.
+        let position = 'search: loop {
+            if last {
+                // Search the first word of the last block.
+                while offset < W::BITS {
+                    let (value, _) = W::window_words(window);
+
+                    window = W::shift_window(window);
+                    offset += 1;
+
+                    if self.comparator.is_match(value) {
+                        break 'search Some(Match::new(&self.comparator, 2 * W::BITS * index + offset - 1, value));
+                    }
+                }
+
+                break 'search None;
+            }
+
+            if offset == 0 {
+                match haystack.next() {
+                    Some(block) => {
+                        next = W::load_double::<O>(block);
+                        window = W::window(current, W::first(next));
+                    }
+                    None => {
+                        last = true;
+                        window = W::window(current, W::ZERO);
+                        continue;
+                    }
+                }
+            }
+
+            // Search the first and second word of the block, one bit at a time,
+            // with a single shift of the window for both offsets where the word size allows it.
+            // Matches in the second word are reported after the first word is fully searched,
+            // so that positions are reported in increasing order.
+            while offset < W::BITS {
+                let (value, second) = W::window_words(window);
+
+                if self.comparator.is_match(second) {
+                    pending = pending | W::ONE << offset;
+                }
+
+                window = W::shift_window(window);
+                offset += 1;
+
+                if self.comparator.is_match(value) {
+                    break 'search Some(Match::new(&self.comparator, 2 * W::BITS * index + offset - 1, value));
+                }
+            }
+
+            if pending != W::ZERO {
+                let pending_offset = pending.trailing_zeros() as usize;
+                pending = pending & (pending - W::ONE);
+
+                // The window is now shifted one word, so the second word is found
+                // by shifting the block from its second word by the pending offset.
+                let original = W::concat(W::second(current), W::first(next));
+                let value = W::first(original << pending_offset);
+                let position = 2 * W::BITS * index + W::BITS + pending_offset;
+                break 'search Some(Match::new(&self.comparator, position, value));
+            }
+
+            // Set "next" as "current" for the next iteration.
+            current = next;
+            offset = 0;
+            index += 1;
+        };
+
+        *cursor = DoubleCursor {
+            current: Some(current),
+            window,
+            next,
+            index,
+            offset,
+            pending,
+            last,
+        };
+
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        comparators::{Exact16Comparator, Exact32Comparator, Exact64Comparator, Exact8Comparator},
+        detectors::testing::{assert_no_match_in_last_possible_position, assert_position, assert_positions_overlapping},
+    };
+
+    use super::*;
+
+    #[test]
+    fn position() {
+        assert_position(Double8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_position(Double16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_position(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_position(Double64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn positions_overlapping() {
+        assert_positions_overlapping(Double8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_positions_overlapping(Double16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_positions_overlapping(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_positions_overlapping(Double64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn no_match_in_last_possible_position() {
+        assert_no_match_in_last_possible_position(Double8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_no_match_in_last_possible_position(Double16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_no_match_in_last_possible_position(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_no_match_in_last_possible_position(
+            Double64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new(),
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use alloc::boxed::Box;
+
+use super::{Detector, Match};
+
+/// An object safe companion to `Detector` that searches byte haystacks.
+/// It is implemented for all detectors regardless of their block type,
+/// so that e.g. a `Box<dyn DynByteDetector<u32>>` can hold any detector for a 32 bit syncword.
+pub trait DynByteDetector<T> {
+    /// The number of bits in the syncword.
+    fn syncword_bits(&self) -> usize;
+
+    /// Search a byte haystack of any length for the syncword, see `Detector::find()`.
+    fn find_in_bytes(&self, haystack: &[u8]) -> Option<Match<T>>;
+
+    /// Search a byte haystack that is the end of the stream for the syncword, see `Detector::finish()`.
+    fn finish_in_bytes(&self, haystack: &[u8]) -> Option<Match<T>>;
+}
+
+/// An object safe companion to `Detector` that searches haystacks given as slices of blocks.
+/// It is implemented for all detectors, so that e.g. a `Box<dyn DynDetector<u32, Block = u32>>`
+/// can hold any detector with 32 bit blocks and be replaced at runtime.
+pub trait DynDetector<T>: DynByteDetector<T> {
+    type Block: Copy;
+
+    /// Create a block from `slice`, see `Detector::from_slice()`.
+    fn block_from_slice(&self, slice: &[u8]) -> Self::Block;
+
+    /// Search a haystack of blocks for the syncword, see `Detector::find_in_blocks()`.
+    fn find_in_slice(&self, haystack: &[Self::Block]) -> Option<Match<T>>;
+
+    /// Search a haystack given as two consecutive slices of blocks for all syncwords,
+    /// see `Detector::matches_in_blocks()`.
+    fn matches_in_slices<'a>(
+        &'a self,
+        first: &'a [Self::Block],
+        second: &'a [Self::Block],
+    ) -> Box<dyn Iterator<Item = Match<T>> + 'a>
+    where
+        T: 'a;
+}
+
+impl<D: Detector<T>, T> DynByteDetector<T> for D {
+    fn syncword_bits(&self) -> usize {
+        D::SYNCWORD_BITS
+    }
+
+    fn find_in_bytes(&self, haystack: &[u8]) -> Option<Match<T>> {
+        self.find(haystack)
+    }
+
+    fn finish_in_bytes(&self, haystack: &[u8]) -> Option<Match<T>> {
+        self.finish(haystack)
+    }
+}
+
+impl<D: Detector<T>, T> DynDetector<T> for D {
+    type Block = D::Block;
+
+    fn block_from_slice(&self, slice: &[u8]) -> Self::Block {
+        D::from_slice(slice)
+    }
+
+    fn find_in_slice(&self, haystack: &[Self::Block]) -> Option<Match<T>> {
+        self.find_in_blocks(haystack.iter().copied())
+    }
+
+    fn matches_in_slices<'a>(
+        &'a self,
+        first: &'a [Self::Block],
+        second: &'a [Self::Block],
+    ) -> Box<dyn Iterator<Item = Match<T>> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.matches_in_blocks(first.iter().chain(second.iter()).copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{Exact32Comparator, RuntimePopCount32Comparator},
+        detectors::{cortexm4, Double32Detector, Single32Detector},
+    };
+
+    use super::*;
+
+    #[test]
+    fn boxed_detectors() {
+        let detectors: Vec<Box<dyn DynDetector<u32, Block = u32>>> = vec![
+            Box::new(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new()),
+            Box::new(cortexm4::sync32::<0xFFFFFFFF, 2>()),
+            Box::new(Single32Detector::with_comparator(RuntimePopCount32Comparator {
+                syncword: 0xFFFF0000,
+                threshold: 0,
+            })),
+        ];
+
+        let haystack = [0x00, 0x3F, 0xFF, 0xFF, 0xFF, 0xF0, 0x00, 0x00];
+        let blocks: Vec<u32> = haystack
+            .chunks_exact(4)
+            .map(|chunk| detectors[0].block_from_slice(chunk))
+            .collect();
+
+        let found: Vec<Option<usize>> = detectors
+            .iter()
+            .map(|d| d.find_in_slice(&blocks).map(|m| m.position))
+            .collect();
+        assert_eq!(vec![Some(10), Some(8), Some(28)], found);
+
+        let found: Vec<Option<usize>> = detectors
+            .iter()
+            .map(|d| d.find_in_bytes(&haystack[1..]).map(|m| m.position))
+            .collect();
+        assert_eq!(vec![Some(2), Some(0), Some(20)], found);
+
+        let found: Vec<Option<usize>> = detectors
+            .iter()
+            .map(|d| d.finish_in_bytes(&haystack[..5]).map(|m| m.position))
+            .collect();
+        assert_eq!(vec![None, Some(8), Some(24)], found);
+
+        let found: Vec<usize> = detectors[1]
+            .matches_in_slices(&blocks[..1], &blocks[1..])
+            .map(|m| m.position)
+            .collect();
+        assert_eq!(vec![8, 9, 10, 11, 12, 13, 14], found);
+    }
+
+    #[test]
+    fn boxed_detectors_with_any_block() {
+        let detectors: Vec<Box<dyn DynByteDetector<u32>>> = vec![
+            Box::new(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new()),
+            Box::new(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new()),
+        ];
+
+        let haystack = [0x00, 0x3F, 0xFF, 0xFF, 0xFF, 0xF0, 0x00, 0x00, 0x00];
+        let found: Vec<Option<usize>> = detectors
+            .iter()
+            .map(|d| d.find_in_bytes(&haystack).map(|m| m.position))
+            .collect();
+        assert_eq!(vec![Some(10), Some(10)], found);
+    }
+}
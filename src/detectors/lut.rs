@@ -0,0 +1,301 @@
+use core::{marker::PhantomData, mem::size_of};
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{RuntimePopCount16Comparator, RuntimePopCount32Comparator},
+    word::Word,
+};
+
+use super::{Detector, Match};
+
+/// Tables of partial Hamming distances to a syncword, generated at compile time.
+/// The tables are constants, i.e. they are placed in flash and use no RAM.
+pub trait LutTables<T> {
+    /// The syncword that the tables are generated for.
+    const SYNCWORD: T;
+
+    /// The number of bytes spanned by the syncword at any of the 8 bit shifts within a byte.
+    const SPAN: usize;
+
+    /// The size of the tables in bytes.
+    const SIZE: usize;
+
+    /// Get the Hamming distance to the syncword of the bits in `bytes`, starting at bit `shift` of the first byte.
+    fn distance(shift: usize, bytes: &[u8]) -> u32;
+}
+
+/// Generate the distance of each byte value at each byte of the syncword, for each of the 8 shifts.
+/// `syncword` is aligned to the most significant bit.
+const fn byte_tables<const SPAN: usize>(syncword: u64, bits: usize) -> [[[u8; 256]; SPAN]; 8] {
+    let mut tables = [[[0u8; 256]; SPAN]; 8];
+    let mask = !(u64::MAX >> bits);
+
+    let mut shift = 0;
+    while shift < 8 {
+        let mut byte = 0;
+        while byte < SPAN {
+            let expected = (syncword >> shift >> (56 - 8 * byte)) as u8;
+            let compared = (mask >> shift >> (56 - 8 * byte)) as u8;
+
+            let mut value = 0;
+            while value < 256 {
+                tables[shift][byte][value] = ((value as u8 ^ expected) & compared).count_ones() as u8;
+                value += 1;
+            }
+            byte += 1;
+        }
+        shift += 1;
+    }
+
+    tables
+}
+
+/// Generate the distance of each nibble value at each nibble of the syncword, for each of the 8 shifts.
+/// `syncword` is aligned to the most significant bit.
+const fn nibble_tables<const NIBBLES: usize>(syncword: u64, bits: usize) -> [[[u8; 16]; NIBBLES]; 8] {
+    let mut tables = [[[0u8; 16]; NIBBLES]; 8];
+    let mask = !(u64::MAX >> bits);
+
+    let mut shift = 0;
+    while shift < 8 {
+        let mut nibble = 0;
+        while nibble < NIBBLES {
+            let expected = (syncword >> shift >> (60 - 4 * nibble)) as u8 & 0x0F;
+            let compared = (mask >> shift >> (60 - 4 * nibble)) as u8 & 0x0F;
+
+            let mut value = 0;
+            while value < 16 {
+                tables[shift][nibble][value] = ((value as u8 ^ expected) & compared).count_ones() as u8;
+                value += 1;
+            }
+            nibble += 1;
+        }
+        shift += 1;
+    }
+
+    tables
+}
+
+macro_rules! impl_tables {
+    ($byte:ident, $nibble:ident, $word:ty, $span:literal) => {
+        /// Byte tables with one entry for each byte value, i.e. one table lookup per syncword byte.
+        pub struct $byte<const SW: $word>;
+
+        impl<const SW: $word> $byte<SW> {
+            const TABLES: &'static [[[u8; 256]; $span]; 8] =
+                &byte_tables::<$span>((SW as u64) << (64 - <$word as Word>::BITS), <$word as Word>::BITS);
+        }
+
+        impl<const SW: $word> LutTables<$word> for $byte<SW> {
+            const SYNCWORD: $word = SW;
+            const SPAN: usize = $span;
+            const SIZE: usize = size_of::<[[[u8; 256]; $span]; 8]>();
+
+            #[inline(always)]
+            fn distance(shift: usize, bytes: &[u8]) -> u32 {
+                let tables = &Self::TABLES[shift];
+
+                let mut distance = 0;
+                for (table, &byte) in tables.iter().zip(bytes) {
+                    distance += table[byte as usize] as u32;
+                }
+                distance
+            }
+        }
+
+        /// Nibble tables with one entry for each nibble value, i.e. 16 times smaller than the byte tables,
+        /// but with two table lookups per syncword byte.
+        pub struct $nibble<const SW: $word>;
+
+        impl<const SW: $word> $nibble<SW> {
+            const TABLES: &'static [[[u8; 16]; 2 * $span]; 8] =
+                &nibble_tables::<{ 2 * $span }>((SW as u64) << (64 - <$word as Word>::BITS), <$word as Word>::BITS);
+        }
+
+        impl<const SW: $word> LutTables<$word> for $nibble<SW> {
+            const SYNCWORD: $word = SW;
+            const SPAN: usize = $span;
+            const SIZE: usize = size_of::<[[[u8; 16]; 2 * $span]; 8]>();
+
+            #[inline(always)]
+            fn distance(shift: usize, bytes: &[u8]) -> u32 {
+                let tables = &Self::TABLES[shift];
+
+                let mut distance = 0;
+                for (tables, &byte) in tables.chunks_exact(2).zip(bytes) {
+                    distance += tables[0][(byte >> 4) as usize] as u32 + tables[1][(byte & 0x0F) as usize] as u32;
+                }
+                distance
+            }
+        }
+    };
+}
+
+impl_tables!(ByteTables16, NibbleTables16, u16, 3);
+impl_tables!(ByteTables32, NibbleTables32, u32, 5);
+
+/// Detector that sums partial Hamming distances from the tables `T` for each candidate offset,
+/// and accepts the offsets with at most `threshold` bit errors.
+/// It needs no shifts of the window or population count, which makes it a candidate for cores without fast shifts,
+/// e.g. Cortex-M0 and AVR. The tables use `T::SIZE` bytes of flash.
+pub struct LutDetector<T, O: BitOrder = MsbFirst> {
+    threshold: u32,
+    tables: PhantomData<T>,
+    bit_order: PhantomData<O>,
+}
+
+/// The state of an ongoing search with a `LutDetector`.
+#[derive(Clone, Copy, Default)]
+pub struct LutCursor {
+    /// The bytes of the current block.
+    block: [u8; 4],
+    /// The number of bytes in `block` that are not yet loaded.
+    remaining: usize,
+    /// The last `SPAN` bytes of the haystack, where the first byte is the byte being searched.
+    recent: [u8; MAX_SPAN],
+    /// The number of bytes loaded from the haystack.
+    loaded: usize,
+    /// The next shift to search in the first byte of `recent`.
+    shift: usize,
+}
+
+/// The largest `LutTables::SPAN`.
+const MAX_SPAN: usize = 5;
+
+impl<T> LutDetector<T> {
+    /// Create a detector accepting at most `threshold` bit errors.
+    pub const fn new(threshold: u32) -> Self {
+        Self {
+            threshold,
+            tables: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<T, O: BitOrder> LutDetector<T, O> {
+    /// Create a detector accepting at most `threshold` bit errors for a haystack with the bit order `O`, e.g. `LsbFirst`.
+    pub const fn with_bit_order(threshold: u32, _bit_order: O) -> Self {
+        Self {
+            threshold,
+            tables: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+macro_rules! impl_lut_detector {
+    ($word:ty, $comparator:ident) => {
+        impl<T: LutTables<$word>, O: BitOrder> Detector<$word> for LutDetector<T, O> {
+            type Block = u32;
+            type Cursor = LutCursor;
+
+            fn syncword(&self) -> $word {
+                T::SYNCWORD
+            }
+
+            fn from_slice(slice: &[u8]) -> Self::Block {
+                u32::from_slice(slice)
+            }
+
+            fn resume<I: Iterator<Item = Self::Block>>(
+                &self,
+                cursor: &mut Self::Cursor,
+                haystack: &mut I,
+            ) -> Option<Match<$word>> {
+                let mut block = cursor.block;
+                let mut remaining = cursor.remaining;
+                let mut recent = cursor.recent;
+                let mut loaded = cursor.loaded;
+                let mut shift = cursor.shift;
+
+                // Iterate for each byte of the haystack one at a time.
+                let position = 'search: loop {
+                    if loaded >= T::SPAN {
+                        // Search all shifts in the first recent byte.
+                        while shift < 8 {
+                            let distance = T::distance(shift, &recent[..T::SPAN]);
+                            shift += 1;
+
+                            if distance <= self.threshold {
+                                let window = recent
+                                    .iter()
+                                    .fold(0u64, |window, &byte| window << 8 | byte as u64)
+                                    << (64 - 8 * MAX_SPAN);
+                                let value = (window << (shift - 1) >> (64 - <$word as Word>::BITS)) as $word;
+                                let comparator = $comparator {
+                                    syncword: T::SYNCWORD,
+                                    threshold: self.threshold,
+                                };
+                                break 'search Some(Match::new(
+                                    &comparator,
+                                    8 * (loaded - T::SPAN) + shift - 1,
+                                    value,
+                                ));
+                            }
+                        }
+                    }
+
+                    if remaining == 0 {
+                        match haystack.next() {
+                            Some(next) => block = next.to_ne_bytes(),
+                            None => break 'search None,
+                        }
+                        remaining = block.len();
+                    }
+
+                    // Load the next byte.
+                    let byte = O::load8(block[block.len() - remaining]);
+                    remaining -= 1;
+
+                    recent.copy_within(1..T::SPAN, 0);
+                    recent[T::SPAN - 1] = byte;
+                    loaded += 1;
+                    shift = 0;
+                };
+
+                *cursor = LutCursor {
+                    block,
+                    remaining,
+                    recent,
+                    loaded,
+                    shift,
+                };
+
+                position
+            }
+        }
+    };
+}
+
+impl_lut_detector!(u16, RuntimePopCount16Comparator);
+impl_lut_detector!(u32, RuntimePopCount32Comparator);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        for value in 0..=u16::MAX {
+            let bytes = value.to_be_bytes();
+            let expected = (value ^ 0x9AC5).count_ones();
+
+            assert_eq!(expected, ByteTables16::<0x9AC5>::distance(0, &[bytes[0], bytes[1], 0xFF]));
+            assert_eq!(expected, NibbleTables16::<0x9AC5>::distance(0, &[bytes[0], bytes[1], 0xFF]));
+
+            // The syncword starts at the third bit of the first byte.
+            let shifted = ((value as u32) << 14).to_be_bytes();
+            assert_eq!(expected, ByteTables16::<0x9AC5>::distance(2, &shifted[..3]));
+            assert_eq!(expected, NibbleTables16::<0x9AC5>::distance(2, &shifted[..3]));
+        }
+    }
+
+    #[test]
+    fn size() {
+        assert_eq!(6144, ByteTables16::<0x9AC5>::SIZE);
+        assert_eq!(768, NibbleTables16::<0x9AC5>::SIZE);
+        assert_eq!(10240, ByteTables32::<0x9AC5_3F1D>::SIZE);
+        assert_eq!(1280, NibbleTables32::<0x9AC5_3F1D>::SIZE);
+    }
+}
@@ -1,23 +1,773 @@
+pub mod best;
+mod bestmatch;
+mod bitap;
+mod bits;
+mod bitsliced;
+pub mod cortexm0;
 pub mod cortexm4;
-mod double16;
-mod double32;
-mod single16;
-mod single32;
+mod double;
+mod dynamic;
+mod lut;
+pub mod portable;
+mod quad;
+pub mod select;
+mod single;
+#[cfg(test)]
+mod testing;
+#[cfg(target_arch = "x86_64")]
+pub mod x86_64;
+
+use core::{marker::PhantomData, mem::size_of};
+
+use crate::comparators::Comparator;
 
 pub trait Detector<T> {
     type Block: Copy + Sized;
 
-    const SYNCWORD: T;
+    /// The state of an ongoing search, allowing it to be resumed after a match.
+    type Cursor: Copy + Default;
+
+    /// The number of bits in the syncword.
+    const SYNCWORD_BITS: usize = 8 * size_of::<T>();
+
+    /// Get the syncword.
+    fn syncword(&self) -> T;
 
     fn from_slice(slice: &[u8]) -> Self::Block;
 
+    /// Continue the search described by `cursor` in the remaining blocks of `haystack`.
+    /// Returns the next syncword match, with its position counted from the start of the search.
+    /// Matches are returned in increasing position order, and overlapping syncwords are all reported.
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<T>>;
+
+    /// Search a haystack for syncword specified by the test algorithm.
+    /// Returns the match at the beginning of the syncword.
+    /// There may be requirements to the length of the haystack for each detector implementation.
+    fn find_in_blocks<I: Iterator<Item = Self::Block>>(&self, haystack: I) -> Option<Match<T>> {
+        let mut haystack = haystack;
+        self.resume(&mut Self::Cursor::default(), &mut haystack)
+    }
+
     /// Search a haystack for syncword specified by the test algorithm.
     /// Returns the bit position of the beginning of the syncword.
     /// There may be requirements to the length of the haystack for each detector implementation.
-    fn position_in_blocks<I: Iterator<Item = Self::Block>>(&self, haystack: I) -> Option<usize>;
+    fn position_in_blocks<I: Iterator<Item = Self::Block>>(&self, haystack: I) -> Option<usize> {
+        self.find_in_blocks(haystack).map(|m| m.position)
+    }
+
+    /// Search a haystack for all syncwords specified by the test algorithm, including overlapping ones.
+    /// Returns an iterator over the matches, in increasing position order.
+    fn matches_in_blocks<I: Iterator<Item = Self::Block>>(
+        &self,
+        haystack: I,
+    ) -> Matches<'_, Self, T, I>
+    where
+        Self: Sized,
+    {
+        Matches {
+            detector: self,
+            haystack,
+            cursor: Self::Cursor::default(),
+            syncword_type: PhantomData,
+        }
+    }
+
+    /// Search a haystack for all syncwords specified by the test algorithm, including overlapping ones.
+    /// Returns an iterator over the bit positions of the beginning of each syncword, in increasing order.
+    fn positions_in_blocks<I: Iterator<Item = Self::Block>>(
+        &self,
+        haystack: I,
+    ) -> Positions<'_, Self, T, I>
+    where
+        Self: Sized,
+    {
+        Positions(self.matches_in_blocks(haystack))
+    }
+
+    /// Search a byte haystack of any length for the syncword.
+    /// Returns the match at the beginning of the syncword.
+    /// Like `find_in_blocks()`, the syncword is not detected in the very last possible position.
+    fn find(&self, haystack: &[u8]) -> Option<Match<T>> {
+        let block_size = size_of::<Self::Block>();
+        let mut chunks = haystack.chunks_exact(block_size);
+
+        // Search all whole blocks.
+        if let Some(m) = self.find_in_blocks((&mut chunks).map(Self::from_slice)) {
+            return Some(m);
+        }
+
+        let remainder = chunks.remainder();
+        if remainder.is_empty() {
+            return None;
+        }
+
+        // The number of blocks needed to cover the positions that were not searched by the whole blocks.
+        let window_bits = 8 * remainder.len() + Self::SYNCWORD_BITS;
+        let window_blocks = ((window_bits + 8 * block_size - 1) / (8 * block_size)).max(2);
+
+        if haystack.len() >= window_blocks * block_size {
+            // Search the trailing bytes in a window of whole blocks that ends with the last byte.
+            // This window overlaps the whole blocks that were already searched, but it does not require any padding.
+            let start = haystack.len() - window_blocks * block_size;
+            let window = haystack[start..]
+                .chunks_exact(block_size)
+                .map(Self::from_slice);
+            self.find_in_blocks(window).map(|m| Match {
+                position: 8 * start + m.position,
+                ..m
+            })
+        } else {
+            // The haystack is shorter than the window, zero pad it and only accept
+            // positions where the syncword is fully within the haystack, but not in the last position.
+            let mut padded = [0u8; 2 * MAX_BLOCK_SIZE];
+            let padded = &mut padded[..window_blocks * block_size];
+            padded[..haystack.len()].copy_from_slice(haystack);
+
+            let blocks = padded.chunks_exact(block_size).map(Self::from_slice);
+            self.find_in_blocks(blocks)
+                .filter(|m| m.position + Self::SYNCWORD_BITS < 8 * haystack.len())
+        }
+    }
+
+    /// Search a byte haystack of any length for the syncword.
+    /// Returns the bit position of the beginning of the syncword.
+    /// Like `position_in_blocks()`, the syncword is not detected in the very last possible position.
+    fn position(&self, haystack: &[u8]) -> Option<usize> {
+        self.find(haystack).map(|m| m.position)
+    }
+
+    /// Search a byte haystack of any length for the syncword, where the haystack is the end of the stream.
+    /// Unlike `find()`, the syncword is also detected in the very last possible position,
+    /// and in the positions where it extends beyond the end of the haystack. The missing bits are zero padded.
+    fn finish(&self, haystack: &[u8]) -> Option<Match<T>> {
+        if let Some(m) = self.find(haystack) {
+            return Some(m);
+        }
+
+        // Search the trailing bytes that contain the positions not searched by `find()`,
+        // padded with zeros so that all of them are before the last possible position.
+        let block_size = size_of::<Self::Block>();
+        let first = (8 * haystack.len()).saturating_sub(Self::SYNCWORD_BITS);
+        let tail = &haystack[first / 8..];
+        let padded_bits = 8 * tail.len() + Self::SYNCWORD_BITS + 1;
+        let padded_blocks = ((padded_bits + 8 * block_size - 1) / (8 * block_size)).max(2);
+
+        let mut padded = [0u8; 4 * MAX_BLOCK_SIZE];
+        let padded = &mut padded[..padded_blocks * block_size];
+        padded[..tail.len()].copy_from_slice(tail);
+
+        let blocks = padded.chunks_exact(block_size).map(Self::from_slice);
+        self.find_in_blocks(blocks)
+            .filter(|m| m.position < 8 * tail.len())
+            .map(|m| Match {
+                position: 8 * (first / 8) + m.position,
+                ..m
+            })
+    }
+}
+
+/// A syncword found by a detector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match<T> {
+    /// The bit position of the beginning of the syncword.
+    pub position: usize,
+    /// The number of syncword bits that were flipped, i.e. the Hamming distance to the syncword.
+    pub errors: u32,
+    /// The syncword bits that were flipped.
+    pub error_mask: T,
+    /// The index of the syncword that was found, for comparators with several syncwords.
+    pub syncword_index: usize,
+    /// Whether the bitwise complement of the syncword was found, for comparators that accept both polarities.
+    pub inverted: bool,
+}
+
+impl<T> Match<T> {
+    /// Create a match for the syncword specified by `comparator` found as `value` at `position`.
+    #[inline(always)]
+    pub fn new<C: Comparator<T>>(comparator: &C, position: usize, value: T) -> Self
+    where
+        T: Copy,
+    {
+        Self {
+            position,
+            errors: comparator.distance(value),
+            error_mask: comparator.error_mask(value),
+            syncword_index: comparator.syncword_index(value),
+            inverted: comparator.is_inverted(value),
+        }
+    }
+}
+
+/// An iterator over all syncword matches in a haystack, see `Detector::matches_in_blocks()`.
+pub struct Matches<'a, D: Detector<T>, T, I> {
+    detector: &'a D,
+    haystack: I,
+    cursor: D::Cursor,
+    syncword_type: PhantomData<T>,
+}
+
+impl<'a, D: Detector<T>, T, I: Iterator<Item = D::Block>> Iterator for Matches<'a, D, T, I> {
+    type Item = Match<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.detector.resume(&mut self.cursor, &mut self.haystack)
+    }
 }
 
+/// An iterator over all syncword positions in a haystack, see `Detector::positions_in_blocks()`.
+pub struct Positions<'a, D: Detector<T>, T, I>(Matches<'a, D, T, I>);
+
+impl<'a, D: Detector<T>, T, I: Iterator<Item = D::Block>> Iterator for Positions<'a, D, T, I> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|m| m.position)
+    }
+}
+
+/// The size in bytes of the largest `Detector::Block`.
+const MAX_BLOCK_SIZE: usize = size_of::<u128>();
+
 pub use self::{
-    double16::Double16Detector, double32::Double32Detector, single16::Single16Detector,
-    single32::Single32Detector,
+    bestmatch::BestMatchDetector,
+    bitap::{Bitap16Detector, Bitap32Detector},
+    bits::BitsDetector,
+    bitsliced::{BitSliced16Detector, BitSliced32Detector},
+    double::{
+        Double16Detector, Double32Detector, Double64Detector, Double8Detector, DoubleDetector,
+    },
+    dynamic::{DynByteDetector, DynDetector},
+    lut::{ByteTables16, ByteTables32, LutDetector, LutTables, NibbleTables16, NibbleTables32},
+    quad::{Quad16Detector, Quad8Detector, QuadDetector},
+    single::{
+        Single16Detector, Single32Detector, Single64Detector, Single8Detector, SingleDetector,
+    },
 };
+
+#[cfg(target_arch = "x86_64")]
+pub use self::x86_64::{Simd16Detector, Simd32Detector};
+
+#[cfg(test)]
+mod tests {
+    use core::{fmt::Debug, mem::size_of, ops::Shr};
+
+    use crate::comparators::{
+        Exact8Comparator, Exact16Comparator, Exact32Comparator, Exact64Comparator, MaskedExact16Comparator,
+        MaskedExact32Comparator, MaskedPopCount16Comparator, MaskedPopCount32Comparator, PopCount8Comparator, PopCount16Comparator,
+        PopCount32Comparator, PopCount64Comparator, RuntimeExact16Comparator,
+        RuntimeExact32Comparator, RuntimePopCount16Comparator, RuntimePopCount32Comparator,
+        RuntimePopCount64Comparator,
+    };
+
+    use super::*;
+    use crate::LsbFirst;
+    use bitvec::prelude::*;
+
+    fn assert_position<D: Detector<T>, T>(detector: D) {
+        let syncword_bits = 8 * size_of::<T>();
+
+        for length in 0..40usize {
+            let bits = 8 * length;
+            for position in 0..bits.saturating_sub(syncword_bits) {
+                let mut haystack = vec![0u8; length];
+
+                {
+                    let bits = haystack.view_bits_mut::<Msb0>();
+
+                    // Insert syncword
+                    for i in 0..syncword_bits {
+                        bits.set(position + i, true);
+                    }
+                }
+
+                let found = detector.position(&haystack);
+
+                assert_eq!(Some(position), found, "Not found in {:?}", haystack);
+            }
+
+            if bits >= syncword_bits {
+                let mut haystack = vec![0u8; length];
+                haystack.view_bits_mut::<Msb0>()[bits - syncword_bits..].set_all(true);
+
+                assert_eq!(None, detector.position(&haystack));
+            }
+        }
+    }
+
+    #[test]
+    fn position_single8() {
+        assert_position(Single8Detector::<Exact8Comparator<0xFF>>::new());
+    }
+
+    #[test]
+    fn position_double8() {
+        assert_position(Double8Detector::<Exact8Comparator<0xFF>>::new());
+    }
+
+    #[test]
+    fn position_quad8() {
+        assert_position(Quad8Detector::<Exact8Comparator<0xFF>>::new());
+    }
+
+    #[test]
+    fn position_single16() {
+        assert_position(Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+
+    #[test]
+    fn position_double16() {
+        assert_position(Double16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+
+    #[test]
+    fn position_quad16() {
+        assert_position(Quad16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+
+    #[test]
+    fn position_bitsliced16() {
+        assert_position(BitSliced16Detector::<PopCount16Comparator<0xFFFF, 0>>::new());
+    }
+
+    #[test]
+    fn position_bitap16() {
+        assert_position(Bitap16Detector::<PopCount16Comparator<0xFFFF, 0>>::new());
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn position_simd16() {
+        assert_position(Simd16Detector::<PopCount16Comparator<0xFFFF, 0>>::new());
+    }
+
+    #[test]
+    fn position_lut16() {
+        assert_position(LutDetector::<ByteTables16<0xFFFF>>::new(0));
+        assert_position(LutDetector::<NibbleTables16<0xFFFF>>::new(0));
+    }
+
+    #[test]
+    fn position_single32() {
+        assert_position(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn position_double32() {
+        assert_position(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn position_single64() {
+        assert_position(Single64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn position_double64() {
+        assert_position(Double64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn position_bitsliced32() {
+        assert_position(BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 0>>::new());
+    }
+
+    #[test]
+    fn position_bitap32() {
+        assert_position(Bitap32Detector::<PopCount32Comparator<0xFFFFFFFF, 0>>::new());
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn position_simd32() {
+        assert_position(Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 0>>::new());
+    }
+
+    #[test]
+    fn position_lut32() {
+        assert_position(LutDetector::<ByteTables32<0xFFFFFFFF>>::new(0));
+        assert_position(LutDetector::<NibbleTables32<0xFFFFFFFF>>::new(0));
+    }
+
+    fn assert_finish<D: Detector<T>, T>(detector: D) {
+        let syncword_bits = 8 * size_of::<T>();
+
+        for length in 0..40usize {
+            let bits = 8 * length;
+            for position in 0..(bits + 1).saturating_sub(syncword_bits) {
+                let mut haystack = vec![0u8; length];
+                haystack.view_bits_mut::<Msb0>()[position..position + syncword_bits].set_all(true);
+
+                assert_eq!(Some(position), detector.finish(&haystack).map(|m| m.position));
+            }
+        }
+    }
+
+    #[test]
+    fn finish_in_last_possible_position() {
+        assert_finish(Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_finish(Double16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_finish(Quad16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_finish(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_finish(Double32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_finish(Single64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+        assert_finish(Double64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn finish_beyond_end() {
+        let detector = Double16Detector::<PopCount16Comparator<0x9AC5, 2>>::new();
+
+        for length in 2..20usize {
+            // Insert the first 14 bits of the syncword at the end, the padded zeros differ in one bit.
+            let mut haystack = vec![0u8; length];
+            let bits = haystack.view_bits_mut::<Msb0>();
+            for i in 0..14 {
+                bits.set(8 * length - 14 + i, (0x9AC5 >> (15 - i)) & 1 == 1);
+            }
+
+            assert_eq!(None, detector.find(&haystack));
+            assert_eq!(
+                Some(Match {
+                    position: 8 * length - 14,
+                    errors: 1,
+                    error_mask: 0x0001,
+                    syncword_index: 0,
+                    inverted: false
+                }),
+                detector.finish(&haystack)
+            );
+        }
+    }
+
+    #[test]
+    fn position_runtime() {
+        assert_position(Single16Detector::with_comparator(RuntimeExact16Comparator {
+            syncword: 0xFFFF,
+        }));
+        assert_position(Double32Detector::with_comparator(RuntimeExact32Comparator {
+            syncword: 0xFFFFFFFF,
+        }));
+    }
+
+    fn assert_masked<D: Detector<T>, T: Copy + Into<u64>>(detector: D, pattern: T) {
+        let syncword_bits = 8 * size_of::<T>();
+        let pattern: u64 = pattern.into();
+
+        for length in 0..40usize {
+            for position in 0..(8 * length).saturating_sub(syncword_bits) {
+                let mut haystack = vec![0u8; length];
+
+                {
+                    let bits = haystack.view_bits_mut::<Msb0>();
+
+                    // Insert syncword where the masked out bits are different from the syncword
+                    for i in 0..syncword_bits {
+                        bits.set(position + i, (pattern >> (syncword_bits - 1 - i)) & 1 == 1);
+                    }
+                }
+
+                let found = detector.find(&haystack);
+
+                assert_eq!(Some(position), found.map(|m| m.position), "Not found in {:?}", haystack);
+                assert_eq!(0, found.unwrap().errors);
+            }
+        }
+    }
+
+    #[test]
+    fn masked_single16() {
+        assert_masked(Single16Detector::<MaskedExact16Comparator<0xFFFF, 0xF0FF>>::new(), 0xF0FFu16);
+    }
+
+    #[test]
+    fn masked_double16() {
+        assert_masked(Double16Detector::<MaskedExact16Comparator<0xFFFF, 0xF0FF>>::new(), 0xF0FFu16);
+        assert_masked(Double16Detector::<MaskedPopCount16Comparator<0xFFFF, 0xF0FF, 1>>::new(), 0xF0FFu16);
+    }
+
+    #[test]
+    fn masked_single32() {
+        assert_masked(Single32Detector::<MaskedExact32Comparator<0xFFFFFFFF, 0xFFFF_FFF0>>::new(), 0xFFFF_FFF0u32);
+    }
+
+    #[test]
+    fn masked_double32() {
+        assert_masked(Double32Detector::<MaskedExact32Comparator<0xFFFFFFFF, 0xFFFF_FFF0>>::new(), 0xFFFF_FFF0u32);
+    }
+
+    #[test]
+    fn masked_bitsliced() {
+        assert_masked(BitSliced16Detector::<MaskedPopCount16Comparator<0xFFFF, 0xF0FF, 1>>::new(), 0xF0FFu16);
+        assert_masked(BitSliced32Detector::<MaskedPopCount32Comparator<0xFFFFFFFF, 0xFFFF_FFF0, 0>>::new(), 0xFFFF_FFF0u32);
+    }
+
+    #[test]
+    fn masked_bitap() {
+        assert_masked(Bitap16Detector::<MaskedPopCount16Comparator<0xFFFF, 0xF0FF, 1>>::new(), 0xF0FFu16);
+        assert_masked(Bitap32Detector::<MaskedPopCount32Comparator<0xFFFFFFFF, 0xFFFF_FFF0, 0>>::new(), 0xFFFF_FFF0u32);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn masked_simd() {
+        assert_masked(Simd16Detector::<MaskedPopCount16Comparator<0xFFFF, 0xF0FF, 1>>::new(), 0xF0FFu16);
+        assert_masked(Simd32Detector::<MaskedPopCount32Comparator<0xFFFFFFFF, 0xFFFF_FFF0, 0>>::new(), 0xFFFF_FFF0u32);
+    }
+
+    #[test]
+    fn masked_cortexm4() {
+        assert_masked(cortexm4::sync16_masked_tol1::<0xFFFF, 0xF0FF>(), 0xF0FFu16);
+        assert_masked(cortexm4::sync32_masked_tol0::<0xFFFFFFFF, 0xFFFF_FFF0>(), 0xFFFF_FFF0u32);
+    }
+
+    fn assert_lsb_first<D: Detector<T>, T: Into<u64>>(detector: D) {
+        let syncword_bits = 8 * size_of::<T>();
+        let syncword: u64 = detector.syncword().into();
+
+        for length in 0..40usize {
+            for position in 0..(8 * length).saturating_sub(syncword_bits) {
+                let mut haystack = vec![0u8; length];
+
+                {
+                    let bits = haystack.view_bits_mut::<Lsb0>();
+
+                    // Insert syncword where the least significant bit of each byte is received first
+                    for i in 0..syncword_bits {
+                        bits.set(position + i, (syncword >> (syncword_bits - 1 - i)) & 1 == 1);
+                    }
+                }
+
+                let found = detector.position(&haystack);
+
+                assert_eq!(Some(position), found, "Not found in {:?}", haystack);
+            }
+        }
+    }
+
+    #[test]
+    fn lsb_first() {
+        assert_lsb_first(Single8Detector::with_bit_order(Exact8Comparator::<0x9A>, LsbFirst));
+        assert_lsb_first(Double8Detector::with_bit_order(Exact8Comparator::<0x9A>, LsbFirst));
+        assert_lsb_first(Single16Detector::with_bit_order(Exact16Comparator::<0x9AC5>, LsbFirst));
+        assert_lsb_first(Double16Detector::with_bit_order(Exact16Comparator::<0x9AC5>, LsbFirst));
+        assert_lsb_first(Quad8Detector::with_bit_order(Exact8Comparator::<0x9A>, LsbFirst));
+        assert_lsb_first(Quad16Detector::with_bit_order(Exact16Comparator::<0x9AC5>, LsbFirst));
+        assert_lsb_first(BitSliced16Detector::with_bit_order(PopCount16Comparator::<0x9AC5, 0>, LsbFirst));
+        assert_lsb_first(BitSliced32Detector::with_bit_order(PopCount32Comparator::<0x9AC5_3F1D, 0>, LsbFirst));
+        assert_lsb_first(Bitap16Detector::with_bit_order(PopCount16Comparator::<0x9AC5, 0>, LsbFirst));
+        assert_lsb_first(Bitap32Detector::with_bit_order(PopCount32Comparator::<0x9AC5_3F1D, 0>, LsbFirst));
+        assert_lsb_first(LutDetector::<ByteTables16<0x9AC5>, _>::with_bit_order(0, LsbFirst));
+        assert_lsb_first(LutDetector::<NibbleTables32<0x9AC5_3F1D>, _>::with_bit_order(0, LsbFirst));
+        assert_lsb_first(Single32Detector::with_bit_order(Exact32Comparator::<0x9AC5_3F1D>, LsbFirst));
+        assert_lsb_first(Double32Detector::with_bit_order(Exact32Comparator::<0x9AC5_3F1D>, LsbFirst));
+        assert_lsb_first(Single64Detector::with_bit_order(
+            Exact64Comparator::<0x9AC5_3F1D_0246_8ACF>,
+            LsbFirst,
+        ));
+        assert_lsb_first(Double64Detector::with_bit_order(
+            Exact64Comparator::<0x9AC5_3F1D_0246_8ACF>,
+            LsbFirst,
+        ));
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn lsb_first_simd() {
+        assert_lsb_first(Simd16Detector::with_bit_order(PopCount16Comparator::<0x9AC5, 0>, LsbFirst));
+        assert_lsb_first(Simd32Detector::with_bit_order(PopCount32Comparator::<0x9AC5_3F1D, 0>, LsbFirst));
+    }
+
+    fn assert_errors<D: Detector<T>, T: Copy + Debug + PartialEq + Shr<usize, Output = T>>(
+        detector: D,
+        top_bit: T,
+    ) {
+        let syncword_bits = 8 * size_of::<T>();
+        let length = 5 * size_of::<D::Block>();
+
+        for position in 0..8 * length - syncword_bits - 1 {
+            // Errors in the first and last bit would make the syncword also match in a neighbouring position.
+            for error in 1..syncword_bits - 1 {
+                let mut haystack = vec![0u8; length];
+
+                {
+                    let bits = haystack.view_bits_mut::<Msb0>();
+
+                    // Insert syncword with a single bit error
+                    for i in 0..syncword_bits {
+                        bits.set(position + i, i != error);
+                    }
+                }
+
+                let found = detector.find(&haystack);
+
+                assert_eq!(
+                    Some(Match {
+                        position,
+                        errors: 1,
+                        error_mask: top_bit >> error,
+                        syncword_index: 0,
+                        inverted: false
+                    }),
+                    found
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn errors_single8() {
+        assert_errors(Single8Detector::<PopCount8Comparator<0xFF, 1>>::new(), 0x80u8);
+    }
+
+    #[test]
+    fn errors_double8() {
+        assert_errors(Double8Detector::<PopCount8Comparator<0xFF, 1>>::new(), 0x80u8);
+    }
+
+    #[test]
+    fn errors_quad8() {
+        assert_errors(Quad8Detector::<PopCount8Comparator<0xFF, 1>>::new(), 0x80u8);
+    }
+
+    #[test]
+    fn errors_single16() {
+        assert_errors(
+            Single16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(),
+            0x8000u16,
+        );
+    }
+
+    #[test]
+    fn errors_double16() {
+        assert_errors(
+            Double16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(),
+            0x8000u16,
+        );
+    }
+
+    #[test]
+    fn errors_quad16() {
+        assert_errors(
+            Quad16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(),
+            0x8000u16,
+        );
+    }
+
+    #[test]
+    fn errors_bitsliced16() {
+        assert_errors(
+            BitSliced16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(),
+            0x8000u16,
+        );
+    }
+
+    #[test]
+    fn errors_bitap16() {
+        assert_errors(Bitap16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(), 0x8000u16);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn errors_simd16() {
+        assert_errors(Simd16Detector::<PopCount16Comparator<0xFFFF, 1>>::new(), 0x8000u16);
+    }
+
+    #[test]
+    fn errors_lut16() {
+        assert_errors(LutDetector::<ByteTables16<0xFFFF>>::new(1), 0x8000u16);
+        assert_errors(LutDetector::<NibbleTables16<0xFFFF>>::new(1), 0x8000u16);
+    }
+
+    #[test]
+    fn errors_single32() {
+        assert_errors(
+            Single32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new(),
+            0x80000000u32,
+        );
+    }
+
+    #[test]
+    fn errors_double32() {
+        assert_errors(
+            Double32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new(),
+            0x80000000u32,
+        );
+    }
+
+    #[test]
+    fn errors_bitsliced32() {
+        assert_errors(
+            BitSliced32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new(),
+            0x80000000u32,
+        );
+    }
+
+    #[test]
+    fn errors_bitap32() {
+        assert_errors(Bitap32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new(), 0x80000000u32);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn errors_simd32() {
+        assert_errors(Simd32Detector::<PopCount32Comparator<0xFFFFFFFF, 1>>::new(), 0x80000000u32);
+    }
+
+    #[test]
+    fn errors_lut32() {
+        assert_errors(LutDetector::<ByteTables32<0xFFFFFFFF>>::new(1), 0x80000000u32);
+        assert_errors(LutDetector::<NibbleTables32<0xFFFFFFFF>>::new(1), 0x80000000u32);
+    }
+
+    #[test]
+    fn errors_single64() {
+        assert_errors(
+            Single64Detector::<PopCount64Comparator<0xFFFFFFFFFFFFFFFF, 1>>::new(),
+            0x8000000000000000u64,
+        );
+    }
+
+    #[test]
+    fn errors_double64() {
+        assert_errors(
+            Double64Detector::<PopCount64Comparator<0xFFFFFFFFFFFFFFFF, 1>>::new(),
+            0x8000000000000000u64,
+        );
+    }
+
+    #[test]
+    fn errors_runtime() {
+        assert_errors(
+            Double16Detector::with_comparator(RuntimePopCount16Comparator {
+                syncword: 0xFFFF,
+                threshold: 1,
+            }),
+            0x8000u16,
+        );
+        assert_errors(
+            Single32Detector::with_comparator(RuntimePopCount32Comparator {
+                syncword: 0xFFFFFFFF,
+                threshold: 1,
+            }),
+            0x80000000u32,
+        );
+        assert_errors(
+            Double64Detector::with_comparator(RuntimePopCount64Comparator {
+                syncword: 0xFFFFFFFFFFFFFFFF,
+                threshold: 1,
+            }),
+            0x8000000000000000u64,
+        );
+        assert_errors(
+            BitSliced32Detector::with_comparator(RuntimePopCount32Comparator {
+                syncword: 0xFFFFFFFF,
+                threshold: 1,
+            }),
+            0x80000000u32,
+        );
+    }
+}
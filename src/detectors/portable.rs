@@ -0,0 +1,35 @@
+use crate::comparators::{
+    Exact16Comparator, Exact32Comparator, TwosComplement16Comparator, TwosComplement32Comparator,
+};
+
+use super::{
+    select::{impl_sync, impl_table},
+    Single16Detector, Single32Detector,
+};
+
+// The default selection for targets without a tuned table, see `detectors::best`.
+// `TwosComplement*` only uses subtraction and and, so it does not depend on CLZ or popcount instructions,
+// and it is the Cortex-M4 selection for most tolerances.
+
+/// The marker type of the portable table, for the `select::Select*` traits.
+pub struct Table;
+
+impl_table!(
+    "for targets without a tuned table";
+    sync16, Sync16, Select16<u16>, 16, "0 to 4";
+    sync32, Sync32, Select32<u32>, 32, "0 to 6"
+);
+
+impl_sync!(sync16_tol0<u16>, Select16<0>, Single16Detector, Exact16Comparator::<SW>);
+impl_sync!(sync16_tol1<u16>, Select16<1>, Single16Detector, TwosComplement16Comparator::<SW, 1>);
+impl_sync!(sync16_tol2<u16>, Select16<2>, Single16Detector, TwosComplement16Comparator::<SW, 2>);
+impl_sync!(sync16_tol3<u16>, Select16<3>, Single16Detector, TwosComplement16Comparator::<SW, 3>);
+impl_sync!(sync16_tol4<u16>, Select16<4>, Single16Detector, TwosComplement16Comparator::<SW, 4>);
+
+impl_sync!(sync32_tol0<u32>, Select32<0>, Single32Detector, Exact32Comparator::<SW>);
+impl_sync!(sync32_tol1<u32>, Select32<1>, Single32Detector, TwosComplement32Comparator::<SW, 1>);
+impl_sync!(sync32_tol2<u32>, Select32<2>, Single32Detector, TwosComplement32Comparator::<SW, 2>);
+impl_sync!(sync32_tol3<u32>, Select32<3>, Single32Detector, TwosComplement32Comparator::<SW, 3>);
+impl_sync!(sync32_tol4<u32>, Select32<4>, Single32Detector, TwosComplement32Comparator::<SW, 4>);
+impl_sync!(sync32_tol5<u32>, Select32<5>, Single32Detector, TwosComplement32Comparator::<SW, 5>);
+impl_sync!(sync32_tol6<u32>, Select32<6>, Single32Detector, TwosComplement32Comparator::<SW, 6>);
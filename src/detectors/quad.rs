@@ -0,0 +1,232 @@
+use core::marker::PhantomData;
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{Comparator, ConstDefault},
+    word::{QuadWord, Word},
+};
+
+use super::{Detector, Match};
+
+/// Detector that searches for a `W` syncword in blocks of four words,
+/// by shifting two quad word windows one bit at a time, i.e. four offsets are compared for each shift.
+#[derive(Default)]
+pub struct QuadDetector<W: QuadWord, C: Comparator<W>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    word: PhantomData<W>,
+    bit_order: PhantomData<O>,
+}
+
+pub type Quad8Detector<C, O = MsbFirst> = QuadDetector<u8, C, O>;
+pub type Quad16Detector<C, O = MsbFirst> = QuadDetector<u16, C, O>;
+
+/// The state of an ongoing search with a `QuadDetector`.
+#[derive(Clone, Copy, Default)]
+pub struct QuadCursor<W: QuadWord> {
+    current: Option<W::Quad>,
+    window: W::Quad,
+    next: W::Quad,
+    index: usize,
+    offset: usize,
+    /// Matches in the last three words of the block that are not yet reported, one bit per offset.
+    pending: W::Quad,
+    /// The haystack is exhausted and only the first three words of the last block remain.
+    last: bool,
+}
+
+impl<W: QuadWord, C: Comparator<W>> QuadDetector<W, C> {
+    /// Create a detector using the comparator with a constant syncword.
+    pub const fn new() -> Self
+    where
+        C: ConstDefault,
+    {
+        Self::with_comparator(C::DEFAULT)
+    }
+
+    /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+    pub const fn with_comparator(comparator: C) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: QuadWord, C: Comparator<W>, O: BitOrder> QuadDetector<W, C, O> {
+    /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+    pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: QuadWord, C: Comparator<W>, O: BitOrder> Detector<W> for QuadDetector<W, C, O> {
+    type Block = W::Quad;
+    type Cursor = QuadCursor<W>;
+
+    fn syncword(&self) -> W {
+        self.comparator.syncword()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self::Block {
+        <W::Quad as Word>::from_slice(slice)
+    }
+
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<W>> {
+        let mut current = match cursor.current {
+            Some(current) => current,
+            None => {
+                // Load the first block.
+                let block = haystack.next()?;
+                W::Quad::load::<O>(block)
+            }
+        };
+        let mut window = cursor.window;
+        let mut next = cursor.next;
+        let mut index = cursor.index;
+        let mut offset = cursor.offset;
+        let mut pending = cursor.pending;
+        let mut last = cursor.last;
+
+        // Iterate for each of the next blocks one at a time.
+        let position = 'search: loop {
+            if last {
+                // Search the first three words of the last block.
+                while offset < 3 * W::BITS {
+                    let value = W::lane(current, 0);
+
+                    current = current << 1;
+                    offset += 1;
+
+                    if self.comparator.is_match(value) {
+                        break 'search Some(Match::new(&self.comparator, 4 * W::BITS * index + offset - 1, value));
+                    }
+                }
+
+                break 'search None;
+            }
+
+            if offset == 0 {
+                match haystack.next() {
+                    Some(block) => {
+                        next = W::Quad::load::<O>(block);
+                        window = current << W::BITS | W::Quad::from(W::lane(next, 0));
+                    }
+                    None => {
+                        last = true;
+                        continue;
+                    }
+                }
+            }
+
+            // Search the first word of the block and the first three words of the window, one bit at a time.
+            // Matches in the window are reported after the first word of the block is fully searched,
+            // so that positions are reported in increasing order.
+            while offset < W::BITS {
+                let value = W::lane(current, 0);
+
+                for lane in 0..3 {
+                    if self.comparator.is_match(W::lane(window, lane)) {
+                        pending = pending | W::Quad::ONE << (lane * W::BITS + offset);
+                    }
+                }
+
+                current = current << 1;
+                window = window << 1;
+                offset += 1;
+
+                if self.comparator.is_match(value) {
+                    break 'search Some(Match::new(&self.comparator, 4 * W::BITS * index + offset - 1, value));
+                }
+            }
+
+            if pending != W::Quad::ZERO {
+                let pending_offset = pending.trailing_zeros() as usize;
+                pending = pending & (pending - W::Quad::ONE);
+
+                // The block is now shifted one word, so the original window is
+                // restored from the block and the first word of the next block.
+                let original = current | W::Quad::from(W::lane(next, 0));
+                let value = W::lane(original << pending_offset, 0);
+                let position = 4 * W::BITS * index + W::BITS + pending_offset;
+                break 'search Some(Match::new(&self.comparator, position, value));
+            }
+
+            // Set "next" as "current" for the next iteration.
+            current = next;
+            offset = 0;
+            index += 1;
+        };
+
+        *cursor = QuadCursor {
+            current: Some(current),
+            window,
+            next,
+            index,
+            offset,
+            pending,
+            last,
+        };
+
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{
+        comparators::{Exact16Comparator, Exact8Comparator},
+        detectors::testing::{assert_no_match_in_last_possible_position, assert_position, assert_positions_overlapping},
+    };
+
+    use super::*;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn position() {
+        assert_position(Quad8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_position(Quad16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+
+    #[test]
+    fn positions_overlapping() {
+        assert_positions_overlapping(Quad8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_positions_overlapping(Quad16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+
+    #[test]
+    fn positions_in_all_lanes() {
+        type D = Quad16Detector<Exact16Comparator<0x9AC5>>;
+        let detector = D::new();
+
+        // Insert the syncword in each of the four lanes of the first block, and in the first lane of the second block.
+        let mut bytes = vec![0u8; 3 * 8];
+        let positions = [3, 21, 37, 54, 64 + 9];
+        for &position in positions.iter() {
+            for i in 0..16 {
+                bytes.view_bits_mut::<Msb0>().set(position + i, (0x9AC5 >> (15 - i)) & 1 == 1);
+            }
+        }
+        let haystack: Vec<u64> = bytes.chunks_exact(8).map(D::from_slice).collect();
+
+        let found: Vec<usize> = detector.positions_in_blocks(haystack.iter().copied()).collect();
+
+        assert_eq!(positions.to_vec(), found);
+    }
+
+    #[test]
+    fn no_match_in_last_possible_position() {
+        assert_no_match_in_last_possible_position(Quad8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_no_match_in_last_possible_position(Quad16Detector::<Exact16Comparator<0xFFFF>>::new());
+    }
+}
@@ -0,0 +1,79 @@
+//! The selection of the fastest detector for a tolerance, shared by the tables of each architecture.
+//! Each table, e.g. `cortexm4`, has a `Table` marker type and implements the `Select*` traits
+//! for the supported tolerances with `impl_sync!`.
+
+use super::Detector;
+
+/// A syncword tolerance, i.e. the number of bits that may differ from the syncword, used for selecting a detector type.
+pub struct Tolerance<const TOL: usize>;
+
+/// The fastest 16 bit detector in `Table` for a tolerance. It is only implemented for the tolerances that are supported.
+pub trait Select16<Table, const SW: u16> {
+    type Detector: Detector<u16>;
+    const DETECTOR: Self::Detector;
+}
+
+/// The fastest 32 bit detector in `Table` for a tolerance. It is only implemented for the tolerances that are supported.
+pub trait Select32<Table, const SW: u32> {
+    type Detector: Detector<u32>;
+    const DETECTOR: Self::Detector;
+}
+
+/// The fastest 64 bit detector in `Table` for a tolerance. It is only implemented for the tolerances that are supported.
+pub trait Select64<Table, const SW: u64> {
+    type Detector: Detector<u64>;
+    const DETECTOR: Self::Detector;
+}
+
+/// Implement the `Select*` traits and the `syncN_tolK` functions for the `Table` of the calling module.
+/// A `LutDetector` is given its tables instead of a comparator, e.g. `LutDetector, ByteTables16::<SW>`.
+macro_rules! impl_sync {
+    ($name:ident<$type:ty>, $select:ident<$tol:literal>, LutDetector, $tables:ident::<SW>) => {
+        impl<const SW: $type> $crate::detectors::select::$select<Table, SW> for $crate::detectors::select::Tolerance<$tol> {
+            type Detector = $crate::detectors::LutDetector<$tables<SW>>;
+            const DETECTOR: Self::Detector = $crate::detectors::LutDetector::new($tol);
+        }
+
+        pub const fn $name<const SW: $type>() -> impl $crate::detectors::Detector<$type> {
+            <$crate::detectors::select::Tolerance<$tol> as $crate::detectors::select::$select<Table, SW>>::DETECTOR
+        }
+    };
+    ($name:ident<$type:ty>, $select:ident<$tol:literal>, $detector:ident, $comparator:ident::<$($arg:tt),*>) => {
+        impl<const SW: $type> $crate::detectors::select::$select<Table, SW> for $crate::detectors::select::Tolerance<$tol> {
+            type Detector = $detector<$comparator<$($arg),*>>;
+            const DETECTOR: Self::Detector = $detector::with_comparator($comparator::<$($arg),*>);
+        }
+
+        pub const fn $name<const SW: $type>() -> impl $crate::detectors::Detector<$type> {
+            <$crate::detectors::select::Tolerance<$tol> as $crate::detectors::select::$select<Table, SW>>::DETECTOR
+        }
+    };
+    ($name:ident<$type:ty, MASK>, $detector:ident, $comparator:expr) => {
+        pub const fn $name<const SW: $type, const MASK: $type>() -> impl $crate::detectors::Detector<$type> {
+            $detector::with_comparator($comparator)
+        }
+    };
+}
+
+/// Define the `Sync*` types and `sync*` functions of the calling module, e.g. `Sync16<SW, TOL>` and `sync16::<SW, TOL>()`,
+/// that name and create the detector selected by its `Table` for a tolerance.
+macro_rules! impl_table {
+    ($target:literal; $($sync:ident, $type_name:ident, $select:ident<$type:ty>, $bits:literal, $tolerances:literal);*) => {
+        $(
+            #[doc = concat!("The fastest detector ", $target, " for the ", $bits, " bit syncword `SW` with the tolerance `TOL` (", $tolerances, ").")]
+            pub type $type_name<const SW: $type, const TOL: usize> =
+                <$crate::detectors::select::Tolerance<TOL> as $crate::detectors::select::$select<Table, SW>>::Detector;
+
+            #[doc = concat!("Create the fastest detector ", $target, " for the ", $bits, " bit syncword `SW` with the tolerance `TOL`.")]
+            pub const fn $sync<const SW: $type, const TOL: usize>() -> $type_name<SW, TOL>
+            where
+                $crate::detectors::select::Tolerance<TOL>: $crate::detectors::select::$select<Table, SW>,
+            {
+                <$crate::detectors::select::Tolerance<TOL> as $crate::detectors::select::$select<Table, SW>>::DETECTOR
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_sync;
+pub(crate) use impl_table;
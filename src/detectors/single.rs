@@ -0,0 +1,172 @@
+use core::marker::PhantomData;
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{Comparator, ConstDefault},
+    word::Word,
+};
+
+use super::{Detector, Match};
+
+/// Detector that searches for a `W` syncword in blocks of one word,
+/// by shifting a window of two words one bit at a time.
+#[derive(Default)]
+pub struct SingleDetector<W: Word, C: Comparator<W>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    word: PhantomData<W>,
+    bit_order: PhantomData<O>,
+}
+
+pub type Single8Detector<C, O = MsbFirst> = SingleDetector<u8, C, O>;
+pub type Single16Detector<C, O = MsbFirst> = SingleDetector<u16, C, O>;
+pub type Single32Detector<C, O = MsbFirst> = SingleDetector<u32, C, O>;
+pub type Single64Detector<C, O = MsbFirst> = SingleDetector<u64, C, O>;
+
+/// The state of an ongoing search with a `SingleDetector`.
+#[derive(Clone, Copy, Default)]
+pub struct SingleCursor<W: Word> {
+    window: Option<W::Double>,
+    index: usize,
+    offset: usize,
+}
+
+impl<W: Word, C: Comparator<W>> SingleDetector<W, C> {
+    /// Create a detector using the comparator with a constant syncword.
+    pub const fn new() -> Self
+    where
+        C: ConstDefault,
+    {
+        Self::with_comparator(C::DEFAULT)
+    }
+
+    /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+    pub const fn with_comparator(comparator: C) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: Word, C: Comparator<W>, O: BitOrder> SingleDetector<W, C, O> {
+    /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+    pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+        Self {
+            comparator,
+            word: PhantomData,
+            bit_order: PhantomData,
+        }
+    }
+}
+
+impl<W: Word, C: Comparator<W>, O: BitOrder> Detector<W> for SingleDetector<W, C, O> {
+    type Block = W;
+    type Cursor = SingleCursor<W>;
+
+    fn syncword(&self) -> W {
+        self.comparator.syncword()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self::Block {
+        W::from_slice(slice)
+    }
+
+    fn resume<I: Iterator<Item = Self::Block>>(
+        &self,
+        cursor: &mut Self::Cursor,
+        haystack: &mut I,
+    ) -> Option<Match<W>> {
+        let mut current = match cursor.window {
+            Some(window) => window,
+            None => {
+                // Load the first block.
+                let block = haystack.next()?;
+                W::concat(W::load::<O>(block), W::ZERO)
+            }
+        };
+        let mut index = cursor.index;
+        let mut offset = cursor.offset;
+
+        // Iterate for each of the next blocks one at a time.
+        let position = 'search: loop {
+            if offset == 0 {
+                match haystack.next() {
+                    Some(block) => current = W::concat(W::first(current), W::load::<O>(block)),
+                    None => break 'search None,
+                }
+            }
+
+            // Search the first word of the double word window, one bit at a time.
+            while offset < W::BITS {
+                let value = W::first(current);
+
+                current = current << 1;
+                offset += 1;
+
+                if self.comparator.is_match(value) {
+                    break 'search Some(Match::new(&self.comparator, W::BITS * index + offset - 1, value));
+                }
+            }
+
+            // "next" is now shifted into "current" for the next iteration.
+            offset = 0;
+            index += 1;
+        };
+
+        *cursor = SingleCursor {
+            window: Some(current),
+            index,
+            offset,
+        };
+
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        comparators::{Exact16Comparator, Exact32Comparator, Exact64Comparator, Exact8Comparator},
+        detectors::testing::{assert_no_match_in_last_possible_position, assert_position, assert_positions_overlapping},
+        LsbFirst,
+    };
+
+    use super::*;
+
+    static DETECTOR: Single16Detector<Exact16Comparator<0x9AC5>> = Single16Detector::new();
+    static LSB_FIRST_DETECTOR: Single16Detector<Exact16Comparator<0x9AC5>, LsbFirst> =
+        Single16Detector::with_bit_order(Exact16Comparator, LsbFirst);
+
+    #[test]
+    fn static_detectors() {
+        assert_eq!(Some(8), DETECTOR.position(&[0x00, 0x9A, 0xC5, 0x00]));
+        assert_eq!(Some(8), LSB_FIRST_DETECTOR.position(&[0x00, 0x59, 0xA3, 0x00]));
+    }
+
+    #[test]
+    fn position() {
+        assert_position(Single8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_position(Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_position(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_position(Single64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn positions_overlapping() {
+        assert_positions_overlapping(Single8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_positions_overlapping(Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_positions_overlapping(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_positions_overlapping(Single64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new());
+    }
+
+    #[test]
+    fn no_match_in_last_possible_position() {
+        assert_no_match_in_last_possible_position(Single8Detector::<Exact8Comparator<0xFF>>::new());
+        assert_no_match_in_last_possible_position(Single16Detector::<Exact16Comparator<0xFFFF>>::new());
+        assert_no_match_in_last_possible_position(Single32Detector::<Exact32Comparator<0xFFFFFFFF>>::new());
+        assert_no_match_in_last_possible_position(
+            Single64Detector::<Exact64Comparator<0xFFFFFFFFFFFFFFFF>>::new(),
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Test helpers shared by the detectors that search in blocks of words, e.g. `SingleDetector` and `DoubleDetector`.
+
+use alloc::vec::Vec;
+use core::{mem::size_of, ops::Range};
+
+use bitvec::prelude::*;
+
+use super::Detector;
+
+/// Create a haystack of `length` blocks where the bits in `ones` are set.
+pub fn haystack<D: Detector<W>, W>(length: usize, ones: Range<usize>) -> Vec<D::Block> {
+    let block_size = size_of::<D::Block>();
+    let mut bytes = vec![0u8; length * block_size];
+    bytes.view_bits_mut::<Msb0>()[ones].set_all(true);
+
+    bytes.chunks_exact(block_size).map(D::from_slice).collect()
+}
+
+pub fn assert_position<D: Detector<W>, W>(detector: D) {
+    for length in 1..10 {
+        let bits = length * size_of::<D::Block>() * 8;
+        for position in 0..bits - D::SYNCWORD_BITS {
+            // Insert the syncword
+            let haystack = haystack::<D, W>(length, position..position + D::SYNCWORD_BITS);
+
+            let found = detector.position_in_blocks(haystack.iter().copied());
+
+            assert_eq!(Some(position), found);
+        }
+    }
+}
+
+pub fn assert_positions_overlapping<D: Detector<W>, W>(detector: D) {
+    for length in 1..10 {
+        let bits = length * size_of::<D::Block>() * 8;
+        for position in 0..bits.saturating_sub(D::SYNCWORD_BITS + 2) {
+            // Insert the syncword extended with two bits, i.e. three overlapping syncwords
+            let haystack = haystack::<D, W>(length, position..position + D::SYNCWORD_BITS + 2);
+
+            let found: Vec<usize> = detector
+                .positions_in_blocks(haystack.iter().copied())
+                .collect();
+
+            assert_eq!(vec![position, position + 1, position + 2], found);
+        }
+    }
+}
+
+pub fn assert_no_match_in_last_possible_position<D: Detector<W>, W>(detector: D) {
+    for length in 1..10 {
+        let bits = length * size_of::<D::Block>() * 8;
+        let position = bits - D::SYNCWORD_BITS;
+
+        // Insert the syncword
+        let haystack = haystack::<D, W>(length, position..bits);
+
+        let found = detector.position_in_blocks(haystack.iter().copied());
+
+        assert_eq!(None, found);
+    }
+}
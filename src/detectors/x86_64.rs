@@ -0,0 +1,532 @@
+use core::{arch::x86_64::*, convert::TryInto, marker::PhantomData, mem::transmute};
+
+use crate::{
+    bitorder::{BitOrder, MsbFirst},
+    comparators::{ConstDefault, HammingComparator, PopCount16Comparator, PopCount32Comparator},
+    word::Word,
+};
+
+use super::{
+    bitsliced::accepted,
+    select::{impl_sync, impl_table},
+    BitSliced32Detector, Detector, Match,
+};
+
+// Output from the x86_64 example, i.e. cargo run --release --features std --example x86_64,
+// as the fastest of 10 rounds of 20 searches for all syncwords in 4096 random bytes, in microseconds
+// single16;exact;507
+// single16;popcnt1;1634
+// single16;popcnt2;2026
+// single16;popcnt3;2302
+// single16;popcnt4;1713
+// single16;twoscmpl1;576
+// single16;twoscmpl2;608
+// single16;twoscmpl3;1102
+// single16;twoscmpl4;982
+// double16;popcnt1;1191
+// double16;popcnt2;1747
+// double16;popcnt3;2254
+// double16;popcnt4;1817
+// double16;twoscmpl1;926
+// double16;twoscmpl2;961
+// double16;twoscmpl3;837
+// double16;twoscmpl4;1026
+// quad16;popcnt1;723
+// quad16;popcnt2;1292
+// quad16;popcnt3;1327
+// quad16;popcnt4;1429
+// quad16;twoscmpl1;670
+// quad16;twoscmpl2;1216
+// quad16;twoscmpl3;823
+// quad16;twoscmpl4;1600
+// bitsliced16;popcnt0;291
+// bitsliced16;popcnt1;521
+// bitsliced16;popcnt2;855
+// bitsliced16;popcnt3;727
+// bitsliced16;popcnt4;1406
+// simd16;popcnt0;173
+// simd16;popcnt1;312
+// simd16;popcnt2;453
+// simd16;popcnt3;542
+// simd16;popcnt4;644
+// single32;exact;360
+// single32;popcnt1;1463
+// single32;popcnt2;1663
+// single32;popcnt3;1370
+// single32;popcnt4;1444
+// single32;popcnt5;1115
+// single32;popcnt6;1112
+// single32;twoscmpl1;549
+// single32;twoscmpl2;1081
+// single32;twoscmpl3;1004
+// single32;twoscmpl4;1316
+// single32;twoscmpl5;1243
+// single32;twoscmpl6;1233
+// double32;popcnt1;1409
+// double32;popcnt2;1279
+// double32;popcnt3;2015
+// double32;popcnt4;1979
+// double32;popcnt5;1908
+// double32;popcnt6;1995
+// double32;twoscmpl1;788
+// double32;twoscmpl2;931
+// double32;twoscmpl3;1179
+// double32;twoscmpl4;1345
+// double32;twoscmpl5;1540
+// double32;twoscmpl6;1729
+// bitsliced32;popcnt0;58
+// bitsliced32;popcnt1;93
+// bitsliced32;popcnt2;338
+// bitsliced32;popcnt3;347
+// bitsliced32;popcnt4;817
+// bitsliced32;popcnt5;830
+// bitsliced32;popcnt6;866
+// simd32;popcnt0;183
+// simd32;popcnt1;348
+// simd32;popcnt2;508
+// simd32;popcnt3;473
+// simd32;popcnt4;945
+// simd32;popcnt5;876
+// simd32;popcnt6;961
+
+/// The instruction set extension used by the SIMD detectors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    /// The bit-sliced counting of `BitSliced32Detector`, one window at a time.
+    Scalar,
+    /// Two windows at a time in 128 bit registers.
+    Sse2,
+    /// Four windows at a time in 256 bit registers.
+    Avx2,
+}
+
+impl Level {
+    /// Get the best level supported by the running processor.
+    /// Without the `std` feature, the level is given by the target features enabled at compile time.
+    #[inline(always)]
+    pub fn detect() -> Self {
+        #[cfg(feature = "std")]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                Level::Avx2
+            } else if std::is_x86_feature_detected!("sse2") {
+                Level::Sse2
+            } else {
+                Level::Scalar
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            if cfg!(target_feature = "avx2") {
+                Level::Avx2
+            } else if cfg!(target_feature = "sse2") {
+                Level::Sse2
+            } else {
+                Level::Scalar
+            }
+        }
+    }
+}
+
+/// Detector that computes the Hamming distance to the syncword at all 128 offsets of a 128 bit block,
+/// using the bit-sliced counters of `BitSliced16Detector` in each 64 bit lane of SSE2 or AVX2 registers.
+/// The instructions are detected at runtime with the `std` feature, with a fallback to the scalar counters.
+#[derive(Default)]
+pub struct Simd16Detector<C: HammingComparator<u16>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// Detector for a 32 bit syncword, see `Simd16Detector`.
+#[derive(Default)]
+pub struct Simd32Detector<C: HammingComparator<u32>, O: BitOrder = MsbFirst> {
+    comparator: C,
+    bit_order: PhantomData<O>,
+}
+
+/// The state of an ongoing search with a SIMD detector.
+#[derive(Clone, Copy, Default)]
+pub struct SimdCursor {
+    current: Option<u128>,
+    next: u128,
+    index: usize,
+    /// The accepted offsets of the current block that are not yet reported, one bit per offset.
+    pending: u128,
+    /// The accepted offsets of the current block are found.
+    loaded: bool,
+    /// The haystack is exhausted and the current block is the last.
+    last: bool,
+}
+
+/// Get the accepted offsets 0..32 of each of the four `windows`, see `bitsliced::accepted()`,
+/// with the windows of the block concatenated so that offset 0 of the first window is the most significant bit.
+#[inline(always)]
+fn accepted_block(level: Level, windows: [u64; 4], syncword: u32, mask: u32, bits: usize, threshold: u32) -> u128 {
+    // A runtime threshold may be larger than the number of syncword bits, which accepts all offsets.
+    let threshold = threshold.min(bits as u32);
+    let accepted = match level {
+        // Safety: The levels are only used when supported.
+        Level::Avx2 => unsafe { accepted_avx2(windows, syncword, mask, bits, threshold) },
+        Level::Sse2 => unsafe {
+            let first = accepted_sse2([windows[0], windows[1]], syncword, mask, bits, threshold);
+            let second = accepted_sse2([windows[2], windows[3]], syncword, mask, bits, threshold);
+            [first[0], first[1], second[0], second[1]]
+        },
+        Level::Scalar => [
+            accepted(windows[0], syncword, mask, bits, threshold),
+            accepted(windows[1], syncword, mask, bits, threshold),
+            accepted(windows[2], syncword, mask, bits, threshold),
+            accepted(windows[3], syncword, mask, bits, threshold),
+        ],
+    };
+
+    (accepted[0] as u128) << 96 | (accepted[1] as u128) << 64 | (accepted[2] as u128) << 32 | accepted[3] as u128
+}
+
+/// The bit-sliced counters of `bitsliced::accepted()` for two windows, where only the upper half of each lane is used.
+#[target_feature(enable = "sse2")]
+unsafe fn accepted_sse2(windows: [u64; 2], syncword: u32, mask: u32, bits: usize, threshold: u32) -> [u32; 2] {
+    let window = _mm_set_epi64x(windows[1] as i64, windows[0] as i64);
+    let ones = _mm_set1_epi32(-1);
+
+    let counter_bits = (32 - threshold.leading_zeros()) as usize;
+    let mut counters = [_mm_setzero_si128(); 6];
+    let mut overflow = _mm_setzero_si128();
+
+    for i in 0..bits {
+        if mask & (0x8000_0000 >> i) == 0 {
+            continue;
+        }
+
+        let haystack = _mm_sll_epi64(window, _mm_cvtsi32_si128(i as i32));
+        let mut carry = if syncword & (0x8000_0000 >> i) != 0 {
+            _mm_xor_si128(haystack, ones)
+        } else {
+            haystack
+        };
+
+        for counter in counters[..counter_bits].iter_mut() {
+            let sum = _mm_xor_si128(*counter, carry);
+            carry = _mm_and_si128(carry, *counter);
+            *counter = sum;
+        }
+        overflow = _mm_or_si128(overflow, carry);
+
+        if _mm_movemask_epi8(_mm_cmpeq_epi32(overflow, ones)) & 0xF0F0 == 0xF0F0 {
+            // No offset can be accepted.
+            return [0; 2];
+        }
+    }
+
+    let mut greater = overflow;
+    let mut equal = ones;
+    for bit in (0..counter_bits).rev() {
+        if threshold & (1 << bit) == 0 {
+            greater = _mm_or_si128(greater, _mm_and_si128(equal, counters[bit]));
+            equal = _mm_andnot_si128(counters[bit], equal);
+        } else {
+            equal = _mm_and_si128(equal, counters[bit]);
+        }
+    }
+
+    let accepted: [u64; 2] = transmute(_mm_andnot_si128(greater, ones));
+    [(accepted[0] >> 32) as u32, (accepted[1] >> 32) as u32]
+}
+
+/// The bit-sliced counters of `bitsliced::accepted()` for four windows, where only the upper half of each lane is used.
+#[target_feature(enable = "avx2")]
+unsafe fn accepted_avx2(windows: [u64; 4], syncword: u32, mask: u32, bits: usize, threshold: u32) -> [u32; 4] {
+    let window = _mm256_set_epi64x(
+        windows[3] as i64,
+        windows[2] as i64,
+        windows[1] as i64,
+        windows[0] as i64,
+    );
+    let ones = _mm256_set1_epi32(-1);
+
+    let counter_bits = (32 - threshold.leading_zeros()) as usize;
+    let mut counters = [_mm256_setzero_si256(); 6];
+    let mut overflow = _mm256_setzero_si256();
+
+    for i in 0..bits {
+        if mask & (0x8000_0000 >> i) == 0 {
+            continue;
+        }
+
+        let haystack = _mm256_sll_epi64(window, _mm_cvtsi32_si128(i as i32));
+        let mut carry = if syncword & (0x8000_0000 >> i) != 0 {
+            _mm256_xor_si256(haystack, ones)
+        } else {
+            haystack
+        };
+
+        for counter in counters[..counter_bits].iter_mut() {
+            let sum = _mm256_xor_si256(*counter, carry);
+            carry = _mm256_and_si256(carry, *counter);
+            *counter = sum;
+        }
+        overflow = _mm256_or_si256(overflow, carry);
+
+        if _mm256_movemask_epi8(_mm256_cmpeq_epi32(overflow, ones)) as u32 & 0xF0F0_F0F0 == 0xF0F0_F0F0 {
+            // No offset can be accepted.
+            return [0; 4];
+        }
+    }
+
+    let mut greater = overflow;
+    let mut equal = ones;
+    for bit in (0..counter_bits).rev() {
+        if threshold & (1 << bit) == 0 {
+            greater = _mm256_or_si256(greater, _mm256_and_si256(equal, counters[bit]));
+            equal = _mm256_andnot_si256(counters[bit], equal);
+        } else {
+            equal = _mm256_and_si256(equal, counters[bit]);
+        }
+    }
+
+    let accepted: [u64; 4] = transmute(_mm256_andnot_si256(greater, ones));
+    [
+        (accepted[0] >> 32) as u32,
+        (accepted[1] >> 32) as u32,
+        (accepted[2] >> 32) as u32,
+        (accepted[3] >> 32) as u32,
+    ]
+}
+
+/// Get the four 64 bit windows starting at each 32 bit word of `current`.
+#[inline(always)]
+fn windows(current: u128, next: u128) -> [u64; 4] {
+    [
+        (current >> 64) as u64,
+        (current >> 32) as u64,
+        current as u64,
+        (current << 32 | next >> 96) as u64,
+    ]
+}
+
+macro_rules! impl_simd {
+    ($name:ident, $word:ty) => {
+        impl<C: HammingComparator<$word>> $name<C> {
+            /// Create a detector using the comparator with a constant syncword.
+            pub const fn new() -> Self
+            where
+                C: ConstDefault,
+            {
+                Self::with_comparator(C::DEFAULT)
+            }
+
+            /// Create a detector using `comparator`, e.g. one where the syncword is configured at runtime.
+            pub const fn with_comparator(comparator: C) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> $name<C, O> {
+            /// Create a detector using `comparator` for a haystack with the bit order `O`, e.g. `LsbFirst`.
+            pub const fn with_bit_order(comparator: C, _bit_order: O) -> Self {
+                Self {
+                    comparator,
+                    bit_order: PhantomData,
+                }
+            }
+        }
+
+        impl<C: HammingComparator<$word>, O: BitOrder> Detector<$word> for $name<C, O> {
+            type Block = u128;
+            type Cursor = SimdCursor;
+
+            fn syncword(&self) -> $word {
+                self.comparator.syncword()
+            }
+
+            fn from_slice(slice: &[u8]) -> Self::Block {
+                u128::from_ne_bytes(slice.try_into().unwrap())
+            }
+
+            fn resume<I: Iterator<Item = Self::Block>>(
+                &self,
+                cursor: &mut Self::Cursor,
+                haystack: &mut I,
+            ) -> Option<Match<$word>> {
+                const BITS: usize = <$word as Word>::BITS;
+                let syncword = (self.comparator.syncword() as u32) << (32 - BITS);
+                let mask = (self.comparator.mask() as u32) << (32 - BITS);
+                let threshold = self.comparator.threshold();
+                let level = Level::detect();
+
+                let mut current = match cursor.current {
+                    Some(current) => current,
+                    None => {
+                        // Load the first 128 bit block.
+                        let block = haystack.next()?;
+                        O::load128(block)
+                    }
+                };
+                let mut next = cursor.next;
+                let mut index = cursor.index;
+                let mut pending = cursor.pending;
+                let mut loaded = cursor.loaded;
+                let mut last = cursor.last;
+
+                // Iterate for each of the next 128 bit blocks one at a time.
+                let position = 'search: loop {
+                    if !loaded {
+                        match haystack.next() {
+                            Some(block) => {
+                                next = O::load128(block);
+                                pending = accepted_block(level, windows(current, next), syncword, mask, BITS, threshold);
+                            }
+                            None if !last => {
+                                // Only the offsets where the syncword is fully within the last block,
+                                // except for the last possible position, are searched.
+                                next = 0;
+                                pending = accepted_block(level, windows(current, next), syncword, mask, BITS, threshold)
+                                    & !(u128::MAX >> (128 - BITS));
+                                last = true;
+                            }
+                            None => break 'search None,
+                        }
+                        loaded = true;
+                    }
+
+                    if pending != 0 {
+                        let offset = pending.leading_zeros() as usize;
+                        pending &= !(1 << (127 - offset));
+
+                        let value = if offset + BITS <= 128 {
+                            (current << offset >> (128 - BITS)) as $word
+                        } else {
+                            (current << offset >> (128 - BITS) | next >> (256 - BITS - offset)) as $word
+                        };
+                        break 'search Some(Match::new(&self.comparator, 128 * index + offset, value));
+                    }
+
+                    if last {
+                        break 'search None;
+                    }
+
+                    // Set "next" as "current" for the next iteration.
+                    current = next;
+                    loaded = false;
+                    index += 1;
+                };
+
+                *cursor = SimdCursor {
+                    current: Some(current),
+                    next,
+                    index,
+                    pending,
+                    loaded,
+                    last,
+                };
+
+                position
+            }
+        }
+    };
+}
+
+impl_simd!(Simd16Detector, u16);
+impl_simd!(Simd32Detector, u32);
+
+/// The marker type of the x86_64 table, for the `select::Select*` traits.
+pub struct Table;
+
+impl_table!(
+    "on an x86_64 host";
+    sync16, Sync16, Select16<u16>, 16, "0 to 4";
+    sync32, Sync32, Select32<u32>, 32, "0 to 6"
+);
+
+// The SIMD detectors are the fastest for the 16 bit syncwords, where a block of 128 bits is searched at once,
+// while the bit-sliced detectors are the fastest for the 32 bit syncwords, as more blocks exceed the tolerance early.
+impl_sync!(sync16_tol0<u16>, Select16<0>, Simd16Detector, PopCount16Comparator::<SW, 0>);
+impl_sync!(sync16_tol1<u16>, Select16<1>, Simd16Detector, PopCount16Comparator::<SW, 1>);
+impl_sync!(sync16_tol2<u16>, Select16<2>, Simd16Detector, PopCount16Comparator::<SW, 2>);
+impl_sync!(sync16_tol3<u16>, Select16<3>, Simd16Detector, PopCount16Comparator::<SW, 3>);
+impl_sync!(sync16_tol4<u16>, Select16<4>, Simd16Detector, PopCount16Comparator::<SW, 4>);
+
+impl_sync!(sync32_tol0<u32>, Select32<0>, BitSliced32Detector, PopCount32Comparator::<SW, 0>);
+impl_sync!(sync32_tol1<u32>, Select32<1>, BitSliced32Detector, PopCount32Comparator::<SW, 1>);
+impl_sync!(sync32_tol2<u32>, Select32<2>, BitSliced32Detector, PopCount32Comparator::<SW, 2>);
+impl_sync!(sync32_tol3<u32>, Select32<3>, BitSliced32Detector, PopCount32Comparator::<SW, 3>);
+impl_sync!(sync32_tol4<u32>, Select32<4>, BitSliced32Detector, PopCount32Comparator::<SW, 4>);
+impl_sync!(sync32_tol5<u32>, Select32<5>, BitSliced32Detector, PopCount32Comparator::<SW, 5>);
+impl_sync!(sync32_tol6<u32>, Select32<6>, BitSliced32Detector, PopCount32Comparator::<SW, 6>);
+
+#[cfg(test)]
+mod tests {
+    use crate::lcg::Lcg;
+
+    use super::*;
+
+    #[test]
+    fn levels() {
+        let mut rng = Lcg::default();
+        let supported: &[Level] = match Level::detect() {
+            Level::Avx2 => &[Level::Sse2, Level::Avx2],
+            Level::Sse2 => &[Level::Sse2],
+            Level::Scalar => &[],
+        };
+
+        for _ in 0..1000 {
+            let syncword = (rng.next_u64() >> 32) as u32;
+
+            // Insert the syncword at a random offset of each window, with a few random bit errors.
+            let mut windows = [0u64; 4];
+            for window in windows.iter_mut() {
+                let state = rng.next_u64();
+                let errors = state & state >> 5 & state >> 11 & state >> 17;
+                *window = ((syncword as u64) << 32 >> (state % 33)) ^ errors;
+            }
+
+            // All bits compared, and about a quarter of the bits masked out.
+            let state = rng.next_u64();
+            for &mask in [u32::MAX, !(state as u32 & (state >> 7) as u32)].iter() {
+                for &bits in [16, 32].iter() {
+                    for threshold in 0..8 {
+                        let syncword = syncword & (u32::MAX << (32 - bits));
+                        let expected = accepted_block(Level::Scalar, windows, syncword, mask, bits, threshold);
+
+                        for &level in supported {
+                            assert_eq!(expected, accepted_block(level, windows, syncword, mask, bits, threshold));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nameable_detectors() {
+        const DETECTOR: Sync32<0x9AC5_3F1D, 2> = sync32::<0x9AC5_3F1D, 2>();
+
+        let haystack = [0x00, 0x9A, 0xC5, 0x3F, 0x1D, 0x00];
+        assert_eq!(Some(8), DETECTOR.position(&haystack));
+        assert_eq!(Some(8), sync16::<0x9AC5, 0>().position(&haystack));
+    }
+
+    #[test]
+    fn threshold_above_word_width() {
+        use crate::comparators::{RuntimePopCount16Comparator, RuntimePopCount32Comparator};
+
+        let detector = Simd16Detector::with_comparator(RuntimePopCount16Comparator { syncword: 0x9AC5, threshold: 64 });
+        assert_eq!(Some(0), detector.position(&[0x00; 32]));
+
+        let detector = Simd32Detector::with_comparator(RuntimePopCount32Comparator { syncword: 0x9AC5_3F1D, threshold: 64 });
+        assert_eq!(Some(0), detector.position(&[0x00; 32]));
+
+        let supported: &[Level] = match Level::detect() {
+            Level::Avx2 => &[Level::Scalar, Level::Sse2, Level::Avx2],
+            Level::Sse2 => &[Level::Scalar, Level::Sse2],
+            Level::Scalar => &[Level::Scalar],
+        };
+        for &level in supported {
+            assert_eq!(u128::MAX, accepted_block(level, [0; 4], 0x9AC5_3F1D, u32::MAX, 32, 64));
+        }
+    }
+}
@@ -8,9 +8,23 @@ pub struct FrameBuffer {
     /// The number if bit shifts (0..7) that needs to be applied to the receive_buffer to make it aligned.
     pub shifts: u8,
     pub frame_len: Option<usize>,
+    /// Whether the frame is received inverted, i.e. bytes added with `extend()` are re-inverted.
+    pub inverted: bool,
+    /// Whether the least significant bit of each byte is received first.
+    /// The shifts then skip the least significant bits of the first byte.
+    pub lsb_first: bool,
 }
 
 impl FrameBuffer {
+    /// Add received bytes to the receive buffer, re-inverting them if the frame is received inverted.
+    pub fn extend(&mut self, bytes: &[u8]) {
+        if self.inverted {
+            self.receive_buffer.extend(bytes.iter().map(|byte| !byte));
+        } else {
+            self.receive_buffer.extend_from_slice(bytes);
+        }
+    }
+
     pub fn is_received(&self) -> bool {
         if let Some(frame_len) = self.frame_len {
             if self.shifts == 0 {
@@ -53,17 +67,28 @@ impl FrameBuffer {
             // Take the relevant bytes from the receive buffer, including that last partial byte
             let unaligned = &self.receive_buffer.as_slice()[range.start..range.end + 1];
 
-            let left_shifts = self.shifts;
-            let right_shifts = 8 - left_shifts;
-
             let mut iter = unaligned.iter();
             let byte = *iter.next().unwrap();
-            let mut partial = byte << left_shifts;
 
             let mut aligned = Vec::with_capacity(range.end - range.start);
-            while let Some(byte) = iter.next().copied() {
-                aligned.push(partial | (byte >> right_shifts));
-                partial = byte << left_shifts;
+            if self.lsb_first {
+                let right_shifts = self.shifts;
+                let left_shifts = 8 - right_shifts;
+
+                let mut partial = byte >> right_shifts;
+                for byte in iter.copied() {
+                    aligned.push(partial | (byte << left_shifts));
+                    partial = byte >> right_shifts;
+                }
+            } else {
+                let left_shifts = self.shifts;
+                let right_shifts = 8 - left_shifts;
+
+                let mut partial = byte << left_shifts;
+                for byte in iter.copied() {
+                    aligned.push(partial | (byte >> right_shifts));
+                    partial = byte << left_shifts;
+                }
             }
 
             aligned
@@ -74,7 +99,12 @@ impl FrameBuffer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{detectors::cortexm4, SyncWindow};
+    use crate::{
+        comparators::{AnyPolarityComparator, Exact32Comparator},
+        detectors::{cortexm4, Single32Detector},
+        Detection, LsbFirst, SyncWindow,
+    };
+    use bitvec::prelude::*;
 
     #[test]
     fn align_without_shifts() {
@@ -82,6 +112,8 @@ mod tests {
             receive_buffer: vec![1, 2, 3],
             shifts: 0,
             frame_len: None,
+            inverted: false,
+            lsb_first: false,
         };
 
         let aligned = frame.get_aligned_part(0..3);
@@ -95,6 +127,8 @@ mod tests {
             receive_buffer: vec![0x70, 0xF0, 0x00],
             shifts: 1,
             frame_len: None,
+            inverted: false,
+            lsb_first: false,
         };
 
         let aligned = frame.get_aligned_part(0..2);
@@ -102,6 +136,21 @@ mod tests {
         assert_eq!(vec![0xE1, 0xE0], aligned);
     }
 
+    #[test]
+    fn align_with_shifts_lsb_first() {
+        let frame = FrameBuffer {
+            receive_buffer: vec![0x0E, 0x0F, 0x00],
+            shifts: 1,
+            frame_len: None,
+            inverted: false,
+            lsb_first: true,
+        };
+
+        let aligned = frame.get_aligned_part(0..2);
+
+        assert_eq!(vec![0x87, 0x07], aligned);
+    }
+
     #[test]
     fn receptions() {
         let mut bs = SyncWindow::new(cortexm4::sync32_tol0::<0xFFFFFFFF>());
@@ -115,11 +164,13 @@ mod tests {
         }
 
         bs.extend(rx);
-        while let Some((shifts, remainder)) = bs.detect().next() {
+        while let Some(Detection { shift, inverted, bytes, .. }) = bs.detect().next() {
             ongoing_receptions.push(FrameBuffer {
-                receive_buffer: remainder,
-                shifts,
+                receive_buffer: bytes,
+                shifts: shift,
                 frame_len: None, // Not yet determined
+                inverted,
+                lsb_first: false,
             });
         }
 
@@ -137,4 +188,76 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn inverted_receptions() {
+        let detector = Single32Detector::<AnyPolarityComparator<Exact32Comparator<0xFFFF0000>>>::new();
+        let mut bs = SyncWindow::new(detector);
+        let mut ongoing_receptions: Vec<FrameBuffer> = vec![];
+
+        // The syncword 0xFFFF0000 followed by the length 2 and the payload 0x12, 0x34 is received inverted and shifted by 4 bits.
+        let rx: [&[u8]; 3] = [
+            &[0xF0, 0x00, 0x0F, 0xFF],
+            &[0xFF, 0xDE, 0xDC, 0xBF],
+            &[0xFF, 0xFF, 0xFF, 0xFF],
+        ];
+
+        for rx in rx.iter() {
+            for rec in ongoing_receptions.iter_mut() {
+                rec.extend(rx);
+            }
+
+            bs.extend(rx);
+            while let Some(Detection { shift, inverted, bytes, .. }) = bs.detect().next() {
+                ongoing_receptions.push(FrameBuffer {
+                    receive_buffer: bytes,
+                    shifts: shift,
+                    frame_len: Some(4 + 1 + 2),
+                    inverted,
+                    lsb_first: false,
+                });
+            }
+        }
+
+        assert_eq!(1, ongoing_receptions.len());
+        assert!(ongoing_receptions[0].inverted);
+        assert!(ongoing_receptions[0].is_received());
+        assert_eq!(
+            vec![0xFF, 0xFF, 0x00, 0x00, 0x02, 0x12, 0x34],
+            ongoing_receptions[0].get_aligned()
+        );
+    }
+
+    #[test]
+    fn lsb_first_receptions() {
+        // The syncword is given in the order the bits are received, i.e. 0x543D54CD with the bits of each byte reversed.
+        let detector = Single32Detector::with_bit_order(Exact32Comparator::<0x2ABC_2AB3>, LsbFirst);
+        let mut bs = SyncWindow::new(detector);
+
+        // The syncword 0x543D54CD followed by the length 1 and the payload 0xA5, sent LSB first and shifted by 3 bits.
+        let frame = [0x54u8, 0x3D, 0x54, 0xCD, 0x01, 0xA5];
+        let mut rx = [0u8; 12];
+        {
+            let bits = rx.view_bits_mut::<Lsb0>();
+            for (i, byte) in frame.iter().enumerate() {
+                for j in 0..8 {
+                    bits.set(3 + 8 * i + j, (byte >> j) & 1 == 1);
+                }
+            }
+        }
+
+        bs.extend(&rx);
+        let Detection { shift, inverted, bytes, .. } = bs.detect().next().unwrap();
+        let received = FrameBuffer {
+            receive_buffer: bytes,
+            shifts: shift,
+            frame_len: Some(4 + 1 + 1),
+            inverted,
+            lsb_first: true,
+        };
+
+        assert_eq!(3, shift);
+        assert!(received.is_received());
+        assert_eq!(frame.to_vec(), received.get_aligned());
+    }
 }
@@ -0,0 +1,25 @@
+/// A linear congruential generator with the constants of Knuth's MMIX,
+/// giving reproducible pseudorandom haystacks for the tests and the autotuner.
+#[derive(Clone)]
+pub struct Lcg {
+    state: u64,
+}
+
+impl Default for Lcg {
+    fn default() -> Self {
+        Self {
+            state: 0x1234_5678_9ABC_DEF0,
+        }
+    }
+}
+
+impl Lcg {
+    /// Get the next pseudorandom value, where the most significant bits are the most random.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        self.state
+    }
+}
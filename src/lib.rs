@@ -1,6 +1,7 @@
 #![feature(const_fn_trait_bound)]
 #![feature(const_impl_trait)]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "std", feature(bench_black_box))]
 
 #[cfg(test)]
 #[macro_use]
@@ -8,11 +9,19 @@ extern crate std;
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod autotune;
+mod bitorder;
 pub mod comparators;
 pub mod detectors;
 mod framebuffer;
+#[cfg(any(test, feature = "std"))]
+mod lcg;
 mod syncwindow;
 mod sliceext;
+mod word;
 
+pub use self::bitorder::{BitOrder, LsbFirst, MsbFirst};
 pub use self::framebuffer::FrameBuffer;
-pub use self::syncwindow::SyncWindow;
+pub use self::syncwindow::{Detection, DynSyncWindow, SyncWindow};
+pub use self::word::{QuadWord, Word};
@@ -1,8 +1,24 @@
 use core::{marker::PhantomData, mem::size_of};
 
-use alloc::{collections::VecDeque, vec::Vec};
-
-use crate::{detectors::Detector, sliceext::SliceExt};
+use alloc::{boxed::Box, collections::VecDeque, vec, vec::Vec};
+
+use crate::{
+    detectors::{Detector, DynDetector, Match},
+    sliceext::SliceExt,
+};
+
+/// A syncword found by `SyncWindow::detect()` or `SyncWindow::flush()`, with the bytes from where it starts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Detection {
+    /// The number of bits that the syncword is shifted in the first byte of `bytes`.
+    pub shift: u8,
+    /// The index of the syncword that was found, see `MultiComparator`.
+    pub syncword_index: usize,
+    /// Whether the syncword was found inverted, see `AnyPolarityComparator`.
+    pub inverted: bool,
+    /// The remaining bytes of the window from the byte where the syncword starts, re-inverted if `inverted`.
+    pub bytes: Vec<u8>,
+}
 
 /// An observation window that holds a series of unaligned bits. Bits can be
 /// added to the window by extending it, and consumed from the window by
@@ -25,89 +41,195 @@ impl<D: Detector<T>, T> SyncWindow<D, T> {
 
     /// Add a series of bytes to be seen by the window.
     pub fn extend(&mut self, bytes: &[u8]) {
-        let block_count = bytes.len() / size_of::<D::Block>();
-        let mut chunks = bytes.chunks_exact(size_of::<D::Block>());
-
-        self.buf.reserve(block_count);
-        
-        while let Some(chunk) = chunks.next() {
-            let block = D::from_slice(chunk);
-            self.buf.push_back(block);
-        }
+        extend(&mut self.buf, bytes, D::from_slice);
+    }
 
-        assert_eq!(0, chunks.remainder().len(), "The number of bytes must be a multiple of the detector block size.");
+    /// Run the detector on the current window and trim it. Returns a `Detection` for each match.
+    pub fn detect(&mut self) -> impl Iterator<Item = Detection> {
+        // Search the two sections of the buffer as one contiguous haystack,
+        // so that a syncword can be found across the wrap.
+        let (first, second) = self.buf.as_slices();
+        let haystack = first.iter().chain(second.iter()).copied();
+
+        let found = self.detector.matches_in_blocks(haystack);
+        let (matches, next_block) = consume(&self.buf, found);
+        trim(&mut self.buf, next_block, D::SYNCWORD_BITS);
+
+        matches.into_iter()
     }
 
-    /// Run the detector on the current window and trim it.
-    pub fn detect(&mut self) -> impl Iterator<Item = (u8, Vec<u8>)> {
-        // TODO: Figure out a way to do this with generators to avoid the vector allocation.
-        let mut matches = Vec::new();
-        while self.buf.len() > 0 {
-            let (first, second) = self.buf.as_slices();
-        
-            // Test first section.
-            let to_remove = if let Some((m, blocks_before)) = self.detect_next(first, second) {
-                matches.push(m);
-
-                // Also remove the block in where the syncword was found to ensure that we do not re-detect the same syncword.
-                blocks_before + 1
-            }
-            else if second.is_empty() {
-                // No more sections, remove all but the last item - it is not fully tested,
-                // as we need to scan from that item into the next arriving.
-                first.len() - 1
-            }
-            else {
-                // Test wrap section.
-                let wrap = [first[first.len() - 1], second[0]];
+    /// Run the detector on the current window as the end of the stream and clear it.
+    /// Unlike `detect()`, the syncword is also detected in the last blocks,
+    /// including where it extends beyond the end of the window. The missing bits are zero padded.
+    pub fn flush(&mut self) -> impl Iterator<Item = Detection> {
+        let bits = 8 * size_of::<D::Block>() * self.buf.len();
+        let padding = D::from_slice(&vec![0u8; size_of::<D::Block>()]);
+        let padding_blocks = padding_blocks::<D::Block>(D::SYNCWORD_BITS);
+
+        let (first, second) = self.buf.as_slices();
+        let haystack = first
+            .iter()
+            .chain(second.iter())
+            .copied()
+            .chain(core::iter::repeat(padding).take(padding_blocks));
+
+        let found = self
+            .detector
+            .matches_in_blocks(haystack)
+            .take_while(|m| m.position < bits);
+        let (matches, _) = consume(&self.buf, found);
+        self.buf.clear();
 
-                if let Some((m, blocks_before)) = self.detect_next(&wrap, &second[1..]) {
-                    matches.push(m);
+        matches.into_iter()
+    }
+}
 
-                    // Remove all blocks until wrap and maybe one more, to ensure that remove
-                    // the block with the detected syncword.
-                    first.len() + blocks_before
-                }
-                else {
-                    // Remove all items until wrap (there is still at least one more item available)
-                    first.len()
-                }
-            };
+/// An observation window like `SyncWindow`, where the detector is a trait object
+/// that can be replaced at runtime, e.g. when the radio changes mode, without losing the buffered bits.
+pub struct DynSyncWindow<T, B> {
+    detector: Box<dyn DynDetector<T, Block = B>>,
+    buf: VecDeque<B>,
+}
 
-            if to_remove == 0 {
-                break;
-            }
-            else if to_remove >= self.buf.len() {
-                self.buf.clear();
-            }
-            else {
-                drop(self.buf.drain(0..to_remove));
-            }
+impl<T, B: Copy> DynSyncWindow<T, B> {
+    /// Create a new `DynSyncWindow`.
+    pub fn new(detector: Box<dyn DynDetector<T, Block = B>>) -> Self {
+        Self {
+            detector,
+            buf: VecDeque::new(),
         }
+    }
+
+    /// Replace the detector, keeping the bits in the window. Returns the previous detector.
+    /// The window holds blocks of the detector block type `B`, so the new detector may be of any type with the same block,
+    /// e.g. a `Single32Detector` and a `BitSliced32Detector` both search `u32` blocks,
+    /// while a detector with a different block type needs a new window.
+    pub fn set_detector(
+        &mut self,
+        detector: Box<dyn DynDetector<T, Block = B>>,
+    ) -> Box<dyn DynDetector<T, Block = B>> {
+        core::mem::replace(&mut self.detector, detector)
+    }
+
+    /// Add a series of bytes to be seen by the window.
+    pub fn extend(&mut self, bytes: &[u8]) {
+        let detector = &self.detector;
+        extend(&mut self.buf, bytes, |chunk| detector.block_from_slice(chunk));
+    }
+
+    /// Run the detector on the current window and trim it, see `SyncWindow::detect()`.
+    pub fn detect(&mut self) -> impl Iterator<Item = Detection> {
+        let (first, second) = self.buf.as_slices();
+
+        let found = self.detector.matches_in_slices(first, second);
+        let (matches, next_block) = consume(&self.buf, found);
+        trim(&mut self.buf, next_block, self.detector.syncword_bits());
 
         matches.into_iter()
     }
 
-    fn detect_next(&self, haystack: &[D::Block], sequel: &[D::Block]) -> Option<((u8, Vec<u8>), usize)> {
-        if let Some(position) = self.detector.position_in_blocks(haystack.iter().copied()) {
-            let byte_index = position / 8;
-            let bit_shifts = (position - byte_index * 8) as u8;
+    /// Run the detector on the current window as the end of the stream and clear it, see `SyncWindow::flush()`.
+    pub fn flush(&mut self) -> impl Iterator<Item = Detection> {
+        let bits = 8 * size_of::<B>() * self.buf.len();
+        let padding = self.detector.block_from_slice(&vec![0u8; size_of::<B>()]);
+        let padding = vec![padding; padding_blocks::<B>(self.detector.syncword_bits())];
 
-            // Copy out the reminder of the buffer into the match.
-            let mut remaining = haystack.as_u8_slice()[byte_index..].to_vec();
-            remaining.extend_from_slice(sequel.as_u8_slice());
+        let (first, second) = self.buf.as_slices();
+        let window = [first, second].concat();
 
-            // Find the number of blocks that was fully consumed before the match.
-            let blocks_before = position / size_of::<D::Block>();
+        let found = self
+            .detector
+            .matches_in_slices(&window, &padding)
+            .take_while(|m| m.position < bits);
+        let (matches, _) = consume(&self.buf, found);
+        self.buf.clear();
 
-            Some(((bit_shifts, remaining), blocks_before))
+        matches.into_iter()
+    }
+}
+
+fn extend<B>(buf: &mut VecDeque<B>, bytes: &[u8], from_slice: impl Fn(&[u8]) -> B) {
+    let block_count = bytes.len() / size_of::<B>();
+    let mut chunks = bytes.chunks_exact(size_of::<B>());
+
+    buf.reserve(block_count);
+
+    for chunk in chunks.by_ref() {
+        let block = from_slice(chunk);
+        buf.push_back(block);
+    }
+
+    assert_eq!(0, chunks.remainder().len(), "The number of bytes must be a multiple of the detector block size.");
+}
+
+/// Copy out the remaining bytes of the buffer for each match.
+/// Returns the matches and the index of the first block after the last match.
+fn consume<B, T>(buf: &VecDeque<B>, found: impl Iterator<Item = Match<T>>) -> (Vec<Detection>, usize) {
+    // TODO: Figure out a way to do this with generators to avoid the vector allocation.
+    let mut matches = Vec::new();
+    let block_bits = 8 * size_of::<B>();
+    let (first, second) = buf.as_slices();
+
+    // No more than one syncword is reported for each block, to ensure that
+    // we do not re-detect the same syncword in overlapping positions.
+    let mut next_block = 0;
+    for m in found {
+        let position = m.position;
+        let block_index = position / block_bits;
+        if block_index < next_block {
+            continue;
         }
-        else {
-            None
+
+        let byte_index = position / 8;
+        let shift = (position - byte_index * 8) as u8;
+
+        // Copy out the reminder of the buffer into the match.
+        let (first, second) = (first.as_u8_slice(), second.as_u8_slice());
+        let mut remaining = if byte_index < first.len() {
+            [&first[byte_index..], second].concat()
+        } else {
+            second[byte_index - first.len()..].to_vec()
+        };
+
+        if m.inverted {
+            for byte in remaining.iter_mut() {
+                *byte = !*byte;
+            }
         }
+
+        matches.push(Detection {
+            shift,
+            syncword_index: m.syncword_index,
+            inverted: m.inverted,
+            bytes: remaining,
+        });
+
+        // Also remove the block in where the syncword was found.
+        next_block = block_index + 1;
     }
+
+    (matches, next_block)
 }
 
+/// The number of zero blocks needed after the window, so that the syncword can be detected in all of its positions.
+fn padding_blocks<B>(syncword_bits: usize) -> usize {
+    let block_bits = 8 * size_of::<B>();
+    (syncword_bits + 1 + block_bits - 1) / block_bits
+}
+
+fn trim<B>(buf: &mut VecDeque<B>, next_block: usize, syncword_bits: usize) {
+    let block_bits = 8 * size_of::<B>();
+
+    // Remove all but the last blocks spanned by the syncword - they are not fully tested,
+    // as we need to scan from those blocks into the next arriving.
+    let untested = (syncword_bits + block_bits - 1) / block_bits;
+    let to_remove = next_block.max(buf.len().saturating_sub(untested));
+    if to_remove >= buf.len() {
+        buf.clear();
+    } else {
+        drop(buf.drain(0..to_remove));
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -115,7 +237,10 @@ mod tests {
 
     use bitvec::prelude::*;
 
-    use crate::detectors::cortexm4;
+    use crate::{
+        comparators::{ExactBitsComparator, MultiComparator, PopCount32Comparator, RuntimeTwosComplement32Comparator},
+        detectors::{cortexm4, BestMatchDetector, BitSliced32Detector, BitsDetector, Single32Detector},
+    };
 
     use super::*;
 
@@ -126,7 +251,7 @@ mod tests {
         bs.extend(rx);
 
         let mut iter = bs.detect();
-        assert_eq!(Some((0, vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -137,7 +262,7 @@ mod tests {
         bs.extend(rx);
 
         let mut iter = bs.detect();
-        assert_eq!(Some((0, vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -148,7 +273,7 @@ mod tests {
         bs.extend(rx);
 
         let mut iter = bs.detect();
-        assert_eq!(Some((1, vec![0x7f, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 1, syncword_index: 0, inverted: false, bytes: vec![0x7f, 0xff, 0xff, 0xff, 0x80, 0x00, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -159,7 +284,27 @@ mod tests {
         bs.extend(rx);
 
         let mut iter = bs.detect();
-        assert_eq!(Some((7, vec![0x01, 0xff, 0xff, 0xff, 0xFE, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 7, syncword_index: 0, inverted: false, bytes: vec![0x01, 0xff, 0xff, 0xff, 0xFE, 0x00, 0x00] }), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn detect_best_match() {
+        let rx = &[0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00];
+
+        // The first accepted position is two bits before the syncword.
+        let mut bs = SyncWindow::new(cortexm4::sync32_tol2::<0xFFFFFFFF>());
+        bs.extend(rx);
+
+        let mut iter = bs.detect();
+        assert_eq!(Some(Detection { shift: 6, syncword_index: 0, inverted: false, bytes: vec![0x00, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00] }), iter.next());
+        assert_eq!(None, iter.next());
+
+        let mut bs = SyncWindow::new(BestMatchDetector::<_, 2>::new(cortexm4::sync32_tol2::<0xFFFFFFFF>()));
+        bs.extend(rx);
+
+        let mut iter = bs.detect();
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -181,7 +326,7 @@ mod tests {
         assert_eq!(1, bs.buf.as_slices().1.len(), "The buffer should wrap for the test to be significant");
 
         let mut iter = bs.detect();
-        assert_eq!(Some((0, vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -203,7 +348,7 @@ mod tests {
         assert_eq!(1, bs.buf.as_slices().1.len(), "The buffer should wrap for the test to be significant");
 
         let mut iter = bs.detect();
-        assert_eq!(Some((4, vec![0x0f, 0xff, 0xff, 0xff, 0xf0, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 4, syncword_index: 0, inverted: false, bytes: vec![0x0f, 0xff, 0xff, 0xff, 0xf0, 0x00] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -226,7 +371,148 @@ mod tests {
         assert_eq!(2, bs.buf.as_slices().1.len(), "The buffer should wrap for the test to be significant");
 
         let mut iter = bs.detect();
-        assert_eq!(Some((0, vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00])), iter.next());
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00] }), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn detect_runtime_syncword() {
+        let comparator = RuntimeTwosComplement32Comparator {
+            syncword: 0x1234_5678,
+            threshold: 1,
+        };
+        let mut bs = SyncWindow::new(Single32Detector::with_comparator(comparator));
+
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x12, 0x34, 0x56, 0x79]);
+        bs.extend(&[0xAA, 0xBB, 0x00, 0x00]);
+
+        let mut iter = bs.detect();
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0x12, 0x34, 0x56, 0x79, 0xAA, 0xBB, 0x00, 0x00] }), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn detect_multiple_syncwords() {
+        let comparator = MultiComparator::new([
+            RuntimeTwosComplement32Comparator {
+                syncword: 0x543D_54CD,
+                threshold: 0,
+            },
+            RuntimeTwosComplement32Comparator {
+                syncword: 0x543D_543D,
+                threshold: 1,
+            },
+        ]);
+        let mut bs = SyncWindow::new(Single32Detector::with_comparator(comparator));
+
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x54, 0x3D, 0x54, 0x3C]);
+        bs.extend(&[0x01, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x54, 0x3D, 0x54, 0xCD]);
+        bs.extend(&[0x02, 0x00, 0x00, 0x00]);
+
+        let found: Vec<(u8, usize, u8)> = bs
+            .detect()
+            .map(|d| (d.shift, d.syncword_index, d.bytes[4]))
+            .collect();
+        assert_eq!(vec![(0, 1, 0x01), (0, 0, 0x02)], found);
+    }
+
+    #[test]
+    fn detect_bits_across_extends() {
+        let mut bs = SyncWindow::new(BitsDetector::<ExactBitsComparator<48, 0xD5A5_1234_5679>>::new());
+
+        let mut data = [0u8; 24];
+        {
+            let bits = data.view_bits_mut::<Msb0>();
+
+            // Insert 48 bit syncword
+            for i in 0..48 {
+                bits.set(61 + i, (0xD5A5_1234_5679u64 >> (47 - i)) & 1 == 1);
+            }
+        }
+
+        let mut found = Vec::new();
+        for chunk in data.chunks_exact(4) {
+            bs.extend(chunk);
+            found.extend(bs.detect());
+        }
+
+        assert_eq!(1, found.len());
+        assert_eq!(5, found[0].shift);
+        assert_eq!(0xD5, found[0].bytes[0] << 5 | found[0].bytes[1] >> 3);
+    }
+
+    #[test]
+    fn detect_after_swapping_detector() {
+        let mut bs = DynSyncWindow::new(Box::new(cortexm4::sync32::<0x543D_54CD, 0>()));
+
+        // The first half of a syncword of the new mode arrives before the detector is swapped.
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x00, 0x00, 0x76, 0x96]);
+        assert_eq!(0, bs.detect().count());
+
+        let previous = bs.set_detector(Box::new(cortexm4::sync32::<0x7696_1234, 1>()));
+        assert_eq!(32, previous.syncword_bits());
+
+        bs.extend(&[0x12, 0x34, 0xAA, 0xBB]);
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+
+        let mut iter = bs.detect();
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0x76, 0x96, 0x12, 0x34, 0xAA, 0xBB, 0x00, 0x00, 0x00, 0x00] }), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn detect_after_swapping_detector_type() {
+        // Raise the tolerance by swapping from a single detector to a bit-sliced detector, both with u32 blocks.
+        let mut bs = DynSyncWindow::new(Box::new(cortexm4::sync32::<0x7696_1234, 0>()));
+
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x00, 0x00, 0x77, 0x97]);
+        assert_eq!(0, bs.detect().count());
+
+        bs.set_detector(Box::new(BitSliced32Detector::<PopCount32Comparator<0x7696_1234, 3>>::new()));
+
+        bs.extend(&[0x12, 0x35, 0xAA, 0xBB]);
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+
+        let mut iter = bs.detect();
+        assert_eq!(
+            Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0x77, 0x97, 0x12, 0x35, 0xAA, 0xBB, 0x00, 0x00, 0x00, 0x00] }),
+            iter.next()
+        );
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn flush_last_possible_position() {
+        let mut bs = SyncWindow::new(cortexm4::sync32_tol0::<0xFFFFFFFF>());
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0xff, 0xff, 0xff, 0xff]);
+
+        assert_eq!(0, bs.detect().count());
+
+        let mut iter = bs.flush();
+        assert_eq!(Some(Detection { shift: 0, syncword_index: 0, inverted: false, bytes: vec![0xff, 0xff, 0xff, 0xff] }), iter.next());
+        assert_eq!(None, iter.next());
+        drop(iter);
+
+        assert!(bs.buf.is_empty());
+    }
+
+    #[test]
+    fn flush_beyond_end() {
+        // The last two bits of the syncword are missing, and the zero padding differs in one of them.
+        let mut bs = DynSyncWindow::new(Box::new(cortexm4::sync32::<0x7696_1235, 1>()));
+        bs.extend(&[0x00, 0x00, 0x00, 0x00]);
+        bs.extend(&[0x1D, 0xA5, 0x84, 0x8D]);
+
+        assert_eq!(0, bs.detect().count());
+
+        let mut iter = bs.flush();
+        assert_eq!(Some(Detection { shift: 2, syncword_index: 0, inverted: false, bytes: vec![0x1D, 0xA5, 0x84, 0x8D] }), iter.next());
         assert_eq!(None, iter.next());
     }
 
@@ -263,8 +549,8 @@ mod tests {
 
                         let mut iter = bs.detect();
                         let m = iter.next().unwrap();
-                        assert_eq!(position % 8, m.0 as usize);
-                        assert!(m.1.len() >= 8);
+                        assert_eq!(position % 8, m.shift as usize);
+                        assert!(m.bytes.len() >= 8);
                         assert_eq!(None, iter.next());
                     }
                 }
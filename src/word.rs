@@ -0,0 +1,239 @@
+use core::{
+    convert::TryInto,
+    ops::{BitAnd, BitOr, Shl, Sub},
+};
+
+use crate::bitorder::BitOrder;
+
+/// A syncword type that the word generic detectors can search for, i.e. `u8`, `u16`, `u32` and `u64`.
+/// The windows of the detectors are made of two words, given by the `Double` type.
+pub trait Word:
+    Copy
+    + Default
+    + PartialEq
+    + BitOr<Output = Self>
+    + BitAnd<Output = Self>
+    + Sub<Output = Self>
+    + Shl<usize, Output = Self>
+{
+    /// The number of bits in the word.
+    const BITS: usize;
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// A type with twice the number of bits of the word.
+    type Double: Copy + Default + Shl<usize, Output = Self::Double>;
+
+    /// The registers of the double detectors, holding a block of two words and the first word of the next block,
+    /// so that the words at an offset and one word later are shifted together.
+    /// It is a single register when three words fit in a native register pair, as with the `u16` words on Cortex-M4.
+    type Window: Copy + Default;
+
+    /// Create a word from the bytes in `slice`, in native byte order.
+    fn from_slice(slice: &[u8]) -> Self;
+
+    /// Create a double word from the bytes in `slice`, in native byte order.
+    fn double_from_slice(slice: &[u8]) -> Self::Double;
+
+    /// Load a block of one word with the bit order `O`, see `BitOrder`.
+    fn load<O: BitOrder>(block: Self) -> Self;
+
+    /// Load a block of two words with the bit order `O`, see `BitOrder`.
+    fn load_double<O: BitOrder>(block: Self::Double) -> Self::Double;
+
+    /// Create a double word where `first` is the most significant word.
+    fn concat(first: Self, second: Self) -> Self::Double;
+
+    /// Get the most significant word of `double`.
+    fn first(double: Self::Double) -> Self;
+
+    /// Get the least significant word of `double`.
+    fn second(double: Self::Double) -> Self;
+
+    fn trailing_zeros(self) -> u32;
+
+    /// Create the window of `block` followed by the word `next`, at offset 0.
+    fn window(block: Self::Double, next: Self) -> Self::Window;
+
+    /// Shift the window one bit.
+    fn shift_window(window: Self::Window) -> Self::Window;
+
+    /// Get the words of the window at the current offset and one word later.
+    fn window_words(window: Self::Window) -> (Self, Self);
+}
+
+/// A word where four words fit in a `Word`, so that the quad detectors can search four lanes in one block.
+pub trait QuadWord: Word {
+    /// A type with four times the number of bits of the word.
+    type Quad: Word + From<Self>;
+
+    /// Get the word in `lane` of `quad`, where lane 0 is the most significant word.
+    fn lane(quad: Self::Quad, lane: usize) -> Self;
+}
+
+macro_rules! impl_word {
+    ($word:ty, $double:ty, $load:ident, $load_double:ident, $window:ident $(<$window_type:ty>)?) => {
+        impl Word for $word {
+            const BITS: usize = <$word>::BITS as usize;
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            type Double = $double;
+
+            #[inline(always)]
+            fn from_slice(slice: &[u8]) -> Self {
+                <$word>::from_ne_bytes(slice.try_into().unwrap())
+            }
+
+            #[inline(always)]
+            fn double_from_slice(slice: &[u8]) -> Self::Double {
+                <$double>::from_ne_bytes(slice.try_into().unwrap())
+            }
+
+            #[inline(always)]
+            fn load<O: BitOrder>(block: Self) -> Self {
+                O::$load(block)
+            }
+
+            #[inline(always)]
+            fn load_double<O: BitOrder>(block: Self::Double) -> Self::Double {
+                O::$load_double(block)
+            }
+
+            #[inline(always)]
+            fn concat(first: Self, second: Self) -> Self::Double {
+                (first as $double) << Self::BITS | second as $double
+            }
+
+            #[inline(always)]
+            fn first(double: Self::Double) -> Self {
+                (double >> Self::BITS) as $word
+            }
+
+            #[inline(always)]
+            fn second(double: Self::Double) -> Self {
+                double as $word
+            }
+
+            #[inline(always)]
+            fn trailing_zeros(self) -> u32 {
+                <$word>::trailing_zeros(self)
+            }
+
+            $window!($word, $double $(, $window_type)?);
+        }
+    };
+}
+
+/// A window in a single register of four words, where the last word is unused.
+macro_rules! single_window {
+    ($word:ty, $double:ty, $quad:ty) => {
+        type Window = $quad;
+
+        #[inline(always)]
+        fn window(block: Self::Double, next: Self) -> Self::Window {
+            (block as $quad) << (2 * Self::BITS) | (next as $quad) << Self::BITS
+        }
+
+        #[inline(always)]
+        fn shift_window(window: Self::Window) -> Self::Window {
+            window << 1
+        }
+
+        #[inline(always)]
+        fn window_words(window: Self::Window) -> (Self, Self) {
+            ((window >> (3 * Self::BITS)) as $word, (window >> (2 * Self::BITS)) as $word)
+        }
+    };
+}
+
+/// A window in two double word registers, the block and the block shifted one word.
+macro_rules! pair_window {
+    ($word:ty, $double:ty) => {
+        type Window = ($double, $double);
+
+        #[inline(always)]
+        fn window(block: Self::Double, next: Self) -> Self::Window {
+            (block, Self::concat(Self::second(block), next))
+        }
+
+        #[inline(always)]
+        fn shift_window(window: Self::Window) -> Self::Window {
+            (window.0 << 1, window.1 << 1)
+        }
+
+        #[inline(always)]
+        fn window_words(window: Self::Window) -> (Self, Self) {
+            (Self::first(window.0), Self::first(window.1))
+        }
+    };
+}
+
+// A 64 bit shift is two instructions on Cortex-M4, while a 128 bit shift is several,
+// so only the windows of the smaller words are kept in a single register.
+impl_word!(u8, u16, load8, load16, single_window<u32>);
+impl_word!(u16, u32, load16, load32, single_window<u64>);
+impl_word!(u32, u64, load32, load64, pair_window);
+impl_word!(u64, u128, load64, load128, pair_window);
+
+macro_rules! impl_quad_word {
+    ($word:ty, $quad:ty) => {
+        impl QuadWord for $word {
+            type Quad = $quad;
+
+            #[inline(always)]
+            fn lane(quad: Self::Quad, lane: usize) -> Self {
+                (quad >> ((3 - lane) * <Self as Word>::BITS)) as $word
+            }
+        }
+    };
+}
+
+impl_quad_word!(u8, u32);
+impl_quad_word!(u16, u64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{LsbFirst, MsbFirst};
+
+    use super::*;
+
+    #[test]
+    fn concat() {
+        let double = u16::concat(0x1234, 0x5678);
+
+        assert_eq!(0x1234_5678, double);
+        assert_eq!(0x1234, u16::first(double));
+        assert_eq!(0x5678, u16::second(double));
+    }
+
+    #[test]
+    fn window() {
+        let window = u16::window(0x1234_5678, 0x9ABC);
+        assert_eq!((0x1234, 0x5678), u16::window_words(window));
+        assert_eq!((0x2468, 0xACF1), u16::window_words(u16::shift_window(window)));
+
+        let window = u32::window(0x1234_5678_9ABC_DEF0, 0x8000_0000);
+        assert_eq!((0x1234_5678, 0x9ABC_DEF0), u32::window_words(window));
+        assert_eq!((0x2468_ACF1, 0x3579_BDE1), u32::window_words(u32::shift_window(window)));
+    }
+
+    #[test]
+    fn lane() {
+        let quad = 0x1234_5678_9ABC_DEF0u64;
+
+        assert_eq!(0x1234, u16::lane(quad, 0));
+        assert_eq!(0x5678, u16::lane(quad, 1));
+        assert_eq!(0x9ABC, u16::lane(quad, 2));
+        assert_eq!(0xDEF0, u16::lane(quad, 3));
+    }
+
+    #[test]
+    fn load() {
+        let block = u16::from_slice(&[0x01, 0x80]);
+
+        assert_eq!(0x0180, u16::load::<MsbFirst>(block));
+        assert_eq!(0x8001, u16::load::<LsbFirst>(block));
+        assert_eq!(0x80, u8::load::<LsbFirst>(0x01));
+    }
+}